@@ -5,6 +5,8 @@ import { renderJavaScriptVisitor } from "@codama/renderers";
 import path from "path";
 import { promises as fs } from "fs";
 
+const IDL_FILE = "solana_staking.json";
+
 // Find the Anchor IDL file and return the JSON object
 const loadAnchorIDL = async () => {
   const basePath = path.join("target", "idl");
@@ -13,19 +15,13 @@ const loadAnchorIDL = async () => {
   try {
     // Read the directory contents
     const files = await fs.readdir(dirPath);
-    const jsonFiles = files.filter((file) => file.endsWith(".json"));
-
-    if (!jsonFiles.length) {
-      throw new Error(`No JSON files found in ${dirPath}`);
-    }
 
-    if (jsonFiles.length > 1) {
-      throw new Error(
-        `Multiple JSON files found in ${dirPath}. Please specify which one to use.`
-      );
+    // The workspace also builds the blacklist hook, only the staking client is generated
+    if (!files.includes(IDL_FILE)) {
+      throw new Error(`${IDL_FILE} not found in ${dirPath}`);
     }
 
-    const filePath = path.join(dirPath, jsonFiles[0]);
+    const filePath = path.join(dirPath, IDL_FILE);
     return JSON.parse(await fs.readFile(filePath, "utf-8"));
   } catch (error) {
     if (error instanceof Error && "code" in error && error.code === "ENOENT") {
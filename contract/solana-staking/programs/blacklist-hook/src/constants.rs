@@ -0,0 +1,9 @@
+// PDA seed constants
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+// Accounts the hook resolves on top of the 5 standard `execute` accounts:
+// staking program, pool state, source blacklist entry, destination blacklist entry
+pub const EXTRA_ACCOUNT_COUNT: usize = 4;
+
+// Offset of `owner` in an SPL token account
+pub const TOKEN_ACCOUNT_OWNER_OFFSET: u8 = 32;
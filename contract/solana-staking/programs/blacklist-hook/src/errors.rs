@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum BlacklistHookError {
+    #[msg("Source owner is blacklisted")]
+    SourceBlacklisted,
+
+    #[msg("Destination owner is blacklisted")]
+    DestinationBlacklisted,
+
+    #[msg("Hook can only be invoked during a token transfer")]
+    NotTransferring,
+}
@@ -0,0 +1,89 @@
+use crate::constants::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use solana_staking::constants::{BLACKLIST_SEED, STATE_SEED};
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: TLV list written by the handler; the layout is fixed for every mint
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(EXTRA_ACCOUNT_COUNT)?,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Extra accounts Token-2022 resolves for every `execute` call. Indices 0-4 are the
+/// source token account, mint, destination token account, authority and this list.
+pub fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    let blacklist_entry_for = |token_account_index: u8| {
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            5,
+            &[
+                Seed::Literal {
+                    bytes: BLACKLIST_SEED.to_vec(),
+                },
+                Seed::AccountKey { index: 6 },
+                Seed::AccountData {
+                    account_index: token_account_index,
+                    data_index: TOKEN_ACCOUNT_OWNER_OFFSET,
+                    length: 32,
+                },
+            ],
+            false,
+            false,
+        )
+    };
+
+    Ok(vec![
+        // 5: staking program
+        ExtraAccountMeta::new_with_pubkey(&solana_staking::ID, false, false)?,
+        // 6: staking pool for this mint
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            5,
+            &[
+                Seed::Literal {
+                    bytes: STATE_SEED.to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            false,
+        )?,
+        // 7: source owner's blacklist entry
+        blacklist_entry_for(0)?,
+        // 8: destination owner's blacklist entry
+        blacklist_entry_for(2)?,
+    ])
+}
+
+pub fn initialize_extra_account_meta_list_handler(
+    ctx: Context<InitializeExtraAccountMetaList>,
+) -> Result<()> {
+    let extra_account_metas = extra_account_metas()?;
+
+    let account_info = ctx.accounts.extra_account_meta_list.to_account_info();
+    let mut data = account_info.try_borrow_mut_data()?;
+    ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &extra_account_metas)?;
+
+    msg!(
+        "Blacklist hook accounts initialized for mint {}",
+        ctx.accounts.mint.key()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use crate::constants::*;
+use crate::errors::BlacklistHookError;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHookAccount;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Account as TokenAccountState;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use solana_staking::constants::{BLACKLIST_SEED, STATE_SEED};
+use solana_staking::utils::is_blacklisted;
+
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Source owner or delegate, already validated by the token program
+    pub authority: UncheckedAccount<'info>,
+
+    /// CHECK: Only used to resolve the accounts below
+    #[account(
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    /// CHECK: Owner program of the pool state and blacklist entries
+    #[account(address = solana_staking::ID)]
+    pub staking_program: UncheckedAccount<'info>,
+
+    /// CHECK: Staking pool for this mint; only used as a PDA seed
+    #[account(
+        seeds = [STATE_SEED, mint.key().as_ref()],
+        bump,
+        seeds::program = solana_staking::ID
+    )]
+    pub state: UncheckedAccount<'info>,
+
+    /// CHECK: This account may or may not exist - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), source_token.owner.as_ref()],
+        bump,
+        seeds::program = solana_staking::ID
+    )]
+    pub source_blacklist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: This account may or may not exist - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), destination_token.owner.as_ref()],
+        bump,
+        seeds::program = solana_staking::ID
+    )]
+    pub destination_blacklist_entry: UncheckedAccount<'info>,
+}
+
+pub fn transfer_hook_handler(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+    // Only Token-2022 may call in, mid-transfer; a direct call proves nothing
+    let source_info = ctx.accounts.source_token.to_account_info();
+    let source_data = source_info.try_borrow_data()?;
+    let source_account = StateWithExtensions::<TokenAccountState>::unpack(&source_data)?;
+    let transferring = source_account
+        .get_extension::<TransferHookAccount>()
+        .map(|extension| bool::from(extension.transferring))
+        .unwrap_or(false);
+    require!(transferring, BlacklistHookError::NotTransferring);
+
+    // Expired entries stop blocking here too, same as in the staking program
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        !is_blacklisted(&ctx.accounts.source_blacklist_entry, now)?,
+        BlacklistHookError::SourceBlacklisted
+    );
+    require!(
+        !is_blacklisted(&ctx.accounts.destination_blacklist_entry, now)?,
+        BlacklistHookError::DestinationBlacklisted
+    );
+
+    msg!(
+        "Transfer of {} approved from {} to {}",
+        amount,
+        ctx.accounts.source_token.owner,
+        ctx.accounts.destination_token.owner
+    );
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use spl_discriminator::SplDiscriminate;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+pub mod constants;
+pub mod errors;
+pub mod instructions;
+
+use instructions::*;
+
+declare_id!("Eg5SSDFnSRhxZSXaz1JGTrgesWH66v8SkxhbrumkK499");
+
+/// Token-2022 transfer hook that rejects transfers to or from addresses on the
+/// solana-staking blacklist of the pool whose staking mint is being transferred.
+#[program]
+pub mod blacklist_hook {
+    use super::*;
+
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        instructions::initialize_extra_account_meta_list::initialize_extra_account_meta_list_handler(
+            ctx,
+        )
+    }
+
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        instructions::transfer_hook::transfer_hook_handler(ctx, amount)
+    }
+}
@@ -3,4 +3,37 @@ pub const STATE_SEED: &[u8] = b"state";
 pub const STAKE_SEED: &[u8] = b"stake";
 pub const STAKING_VAULT_SEED: &[u8] = b"staking_vault";
 pub const REWARD_VAULT_SEED: &[u8] = b"reward_vault";
-pub const BLACKLIST_SEED: &[u8] = b"blacklist";
\ No newline at end of file
+pub const BLACKLIST_SEED: &[u8] = b"blacklist";
+pub const REWARD_STREAM_SEED: &[u8] = b"reward_stream";
+pub const STREAM_VAULT_SEED: &[u8] = b"stream_vault";
+pub const RECEIPT_MINT_SEED: &[u8] = b"receipt_mint";
+pub const ROLE_SEED: &[u8] = b"role";
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+// Accounts the receipt hook resolves on top of the 5 standard `execute` accounts:
+// pool state, both stake positions, both blacklist entries and every reward stream slot
+pub const RECEIPT_HOOK_EXTRA_ACCOUNT_COUNT: usize = 5 + MAX_REWARD_STREAMS;
+
+// Offset of `owner` in an SPL token account
+pub const TOKEN_ACCOUNT_OWNER_OFFSET: u8 = 32;
+
+// Extra reward streams a pool can register on top of its primary reward mint
+pub const MAX_REWARD_STREAMS: usize = 4;
+
+// Scale factor for GlobalState.acc_reward_per_share
+pub const ACC_PRECISION: u128 = 1_000_000_000_000;
+
+// Lockup tiers
+pub const BPS_DENOMINATOR: u16 = 10_000;
+pub const MAX_LOCK_TIERS: usize = 4;
+pub const MAX_LOCK_MULTIPLIER_BPS: u16 = 50_000; // 5x
+
+// Allowlist Merkle tree hashing domains
+pub const ALLOWLIST_LEAF_PREFIX: u8 = 0;
+pub const ALLOWLIST_NODE_PREFIX: u8 = 1;
+
+// Pause flags (GlobalState.paused bitmask)
+pub const PAUSE_STAKE: u8 = 1 << 0;
+pub const PAUSE_UNSTAKE: u8 = 1 << 1;
+pub const PAUSE_CLAIM: u8 = 1 << 2;
+pub const PAUSE_ALL: u8 = PAUSE_STAKE | PAUSE_UNSTAKE | PAUSE_CLAIM;
@@ -28,4 +28,124 @@ pub enum StakingError {
 
     #[msg("Address is already in blacklist")]
     AddressAlreadyBlacklisted,
+
+    #[msg("Cannot withdraw 0 tokens")]
+    InvalidWithdrawAmount,
+
+    #[msg("Cannot withdraw reward tokens into the staking vault")]
+    InvalidWithdrawDestination,
+
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+
+    #[msg("Operation is paused")]
+    OperationPaused,
+
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+
+    #[msg("Invalid reward per second")]
+    InvalidRewardPerSecond,
+
+    #[msg("Operation not supported in this reward mode")]
+    InvalidRewardMode,
+
+    #[msg("All reward streams must be passed as remaining accounts")]
+    MissingRewardStreams,
+
+    #[msg("Invalid reward stream account")]
+    InvalidRewardStream,
+
+    #[msg("Maximum number of reward streams reached")]
+    TooManyRewardStreams,
+
+    #[msg("Invalid reward campaign window")]
+    InvalidRewardWindow,
+
+    #[msg("Invalid lock tier")]
+    InvalidLockTier,
+
+    #[msg("Stake is still locked")]
+    StakeLocked,
+
+    #[msg("Invalid cooldown period")]
+    InvalidCooldown,
+
+    #[msg("Unstaking requires a cooldown, use request_unstake")]
+    CooldownRequired,
+
+    #[msg("No pending withdrawal")]
+    NoPendingWithdrawal,
+
+    #[msg("Cooldown period has not elapsed")]
+    CooldownNotElapsed,
+
+    #[msg("Receipt mint can only be enabled while nothing is staked")]
+    ReceiptMintUnavailable,
+
+    #[msg("Receipt mint and receipt token account are required")]
+    MissingReceiptAccounts,
+
+    #[msg("Compounding requires the reward mint to equal the staking mint")]
+    CompoundUnavailable,
+
+    #[msg("Mint has an unsupported Token-2022 extension")]
+    UnsupportedMintExtension,
+
+    #[msg("Blacklist entry accounts must match the addresses one-to-one")]
+    BlacklistBatchMismatch,
+
+    #[msg("Blacklist entry account does not match its address")]
+    InvalidBlacklistEntry,
+
+    #[msg("Signer is neither the admin nor a blacklist manager")]
+    NotBlacklistManager,
+
+    #[msg("Blacklist expiry must be in the future")]
+    InvalidBlacklistExpiry,
+
+    #[msg("No quarantine owner has been configured")]
+    QuarantineNotSet,
+
+    #[msg("Force unstake is unavailable on pools with a receipt mint")]
+    ForceUnstakeUnavailable,
+
+    #[msg("Nothing staked or unbonding for this address")]
+    NothingToForceUnstake,
+
+    #[msg("A quarantine reward account is required unless rewards are cancelled")]
+    MissingQuarantineRewardAccount,
+
+    #[msg("Wallet is not on the pool allowlist")]
+    NotAllowlisted,
+
+    #[msg("Stake account still holds stake, unbonding tokens or unclaimed rewards")]
+    StakeAccountNotEmpty,
+
+    #[msg("Cannot fund 0 tokens")]
+    InvalidFundAmount,
+
+    #[msg("Cannot stake for the zero address")]
+    InvalidBeneficiary,
+
+    #[msg("Signer is neither the staker nor their claim delegate")]
+    NotClaimAuthority,
+
+    #[msg("Reward account does not belong to the registered reward recipient")]
+    InvalidRewardRecipient,
+
+    #[msg("Reward recipient and claim delegate cannot be the zero address")]
+    InvalidClaimSettings,
+
+    #[msg("Receipt hook can only be invoked during a token transfer")]
+    NotTransferring,
+
+    #[msg("Reward recipient's blacklist entry is required")]
+    MissingRecipientBlacklistEntry,
+
+    #[msg("Lock-boosted positions from the previous rate change must be checkpointed first")]
+    StaleBoostedPositions,
+
+    #[msg("Account is not a stake position of this pool")]
+    InvalidStakePosition,
 }
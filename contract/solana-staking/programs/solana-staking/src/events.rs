@@ -1,9 +1,12 @@
+use crate::state::{LockTier, RewardMode, Role};
 use anchor_lang::prelude::*;
 
 #[event]
 pub struct Staked {
     pub user: Pubkey,
     pub amount: u64,
+    pub lock_until: i64,
+    pub multiplier_bps: u16,
     pub timestamp: i64,
 }
 
@@ -18,6 +21,7 @@ pub struct Unstaked {
 #[event]
 pub struct RewardsClaimed {
     pub user: Pubkey,
+    pub recipient: Pubkey,
     pub amount: u64,
     pub timestamp: i64,
 }
@@ -27,20 +31,269 @@ pub struct Initialized {
     pub authority: Pubkey,
     pub staking_mint: Pubkey,
     pub reward_mint: Pubkey,
+    pub reward_mode: RewardMode,
     pub reward_rate: u64,
+    pub reward_per_second: u64,
     pub timestamp: i64,
 }
 
 #[event]
 pub struct AddedToBlacklist {
     pub address: Pubkey,
-    pub admin: Pubkey,
+    pub admin: Pubkey,     // Pool admin at the time of the change
+    pub authority: Pubkey, // Signer: the admin or a blacklist manager
+    pub reason_code: u16,
+    pub reference: Option<[u8; 32]>,
+    pub expires_at: Option<i64>,
     pub timestamp: i64,
 }
 
 #[event]
 pub struct RemovedFromBlacklist {
     pub address: Pubkey,
+    pub admin: Pubkey,     // Pool admin at the time of the change
+    pub authority: Pubkey, // Signer: the admin or a blacklist manager
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardRateUpdated {
+    pub admin: Pubkey,
+    pub old_rate: u64,
+    pub new_rate: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardTokensWithdrawn {
+    pub admin: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub total_withdrawn: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferAccepted {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Paused {
+    pub admin: Pubkey,
+    pub flags: u8,
+    pub paused: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Unpaused {
+    pub admin: Pubkey,
+    pub flags: u8,
+    pub paused: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardStreamAdded {
+    pub admin: Pubkey,
+    pub reward_stream: Pubkey,
+    pub index: u8,
+    pub reward_mint: Pubkey,
+    pub reward_per_second: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StreamRewardsClaimed {
+    pub user: Pubkey,
+    pub reward_stream: Pubkey,
+    pub reward_mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardCampaignUpdated {
+    pub admin: Pubkey,
+    pub rewards_start: i64,
+    pub rewards_end: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LockTiersUpdated {
+    pub admin: Pubkey,
+    pub tiers: Vec<LockTier>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CooldownUpdated {
+    pub admin: Pubkey,
+    pub cooldown_seconds: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnstakeRequested {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub unbonding_amount: u64,
+    pub available_at: i64,
+    pub rewards: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Withdrawn {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnstakeCancelled {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub rewards: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReceiptMintEnabled {
+    pub admin: Pubkey,
+    pub receipt_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReceiptTransferred {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Compounded {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub new_stake: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub admin: Pubkey,
+    pub role: Role,
+    pub member: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub admin: Pubkey,
+    pub role: Role,
+    pub member: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QuarantineUpdated {
+    pub admin: Pubkey,
+    pub quarantine: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Stream rewards cancelled by a force unstake, in the stream's own reward mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct StreamRewardsCancelled {
+    pub reward_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ForceUnstaked {
+    pub admin: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,           // Active stake seized
+    pub unbonding_amount: u64, // Pending withdrawal seized
+    pub quarantine_account: Pubkey,
+    pub rewards_paid: u64, // Primary rewards sent to the quarantine reward account
+    pub rewards_cancelled: u64, // Primary rewards left in the vault, including any shortfall
+    pub stream_rewards_cancelled: Vec<StreamRewardsCancelled>, // Streams with a non-zero amount
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AllowlistRootUpdated {
     pub admin: Pubkey,
+    pub allowlist_root: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeAccountClosed {
+    pub user: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardShortfall {
+    pub user: Pubkey,
+    pub owed: u64,
+    pub paid: u64,
+    pub unpaid: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsFunded {
+    pub funder: Pubkey,
+    pub amount: u64, // Received by the vault, after any transfer fee
+    pub total_funded: u64,
+    pub vault_balance: u64,
+    pub runway_seconds: Option<u64>, // Gross of owed rewards; only when requested and emitting
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakedFor {
+    pub payer: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimSettingsUpdated {
+    pub user: Pubkey,
+    pub reward_recipient: Option<Pubkey>,
+    pub claim_delegate: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeAccountOpened {
+    pub payer: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PositionsCheckpointed {
+    pub caller: Pubkey,
+    pub count: u32,                   // Positions brought up to the current epoch
+    pub stale_boosted_positions: u32, // Still to checkpoint before the next rate change
     pub timestamp: i64,
 }
@@ -0,0 +1,44 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::AdminTransferAccepted;
+use crate::state::GlobalState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        constraint = state.pending_admin != Pubkey::default() @ StakingError::NotPendingAdmin,
+        has_one = pending_admin @ StakingError::NotPendingAdmin
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn accept_admin_handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+
+    let previous_admin = state.admin;
+    state.admin = ctx.accounts.pending_admin.key();
+    state.pending_admin = Pubkey::default();
+
+    let clock = Clock::get()?;
+
+    msg!(
+        "Admin transferred from {} to {}",
+        previous_admin,
+        state.admin
+    );
+
+    // Emit event
+    emit!(AdminTransferAccepted {
+        previous_admin,
+        new_admin: state.admin,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
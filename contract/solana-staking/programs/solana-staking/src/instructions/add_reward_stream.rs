@@ -0,0 +1,91 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::RewardStreamAdded;
+use crate::state::{GlobalState, RewardStream};
+use crate::utils::validate_mint_extensions;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct AddRewardStream<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = admin
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RewardStream::INIT_SPACE,
+        seeds = [REWARD_STREAM_SEED, state.key().as_ref(), &[state.reward_stream_count]],
+        bump
+    )]
+    pub reward_stream: Box<Account<'info, RewardStream>>,
+
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        token::mint = reward_mint,
+        token::authority = state,
+        seeds = [STREAM_VAULT_SEED, reward_stream.key().as_ref()],
+        bump
+    )]
+    pub stream_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn add_reward_stream_handler(
+    ctx: Context<AddRewardStream>,
+    reward_per_second: u64,
+) -> Result<()> {
+    require!(
+        (ctx.accounts.state.reward_stream_count as usize) < MAX_REWARD_STREAMS,
+        StakingError::TooManyRewardStreams
+    );
+    require!(reward_per_second > 0, StakingError::InvalidRewardPerSecond);
+    validate_mint_extensions(&ctx.accounts.reward_mint)?;
+
+    let state = &mut ctx.accounts.state;
+    let reward_stream = &mut ctx.accounts.reward_stream;
+    let clock = Clock::get()?;
+
+    reward_stream.state = state.key();
+    reward_stream.index = state.reward_stream_count;
+    reward_stream.reward_mint = ctx.accounts.reward_mint.key();
+    reward_stream.reward_vault = ctx.accounts.stream_vault.key();
+    reward_stream.reward_per_second = reward_per_second;
+    reward_stream.acc_reward_per_share = 0;
+    reward_stream.last_update_time = clock.unix_timestamp;
+    reward_stream.bump = ctx.bumps.reward_stream;
+
+    state.reward_stream_count += 1;
+
+    msg!(
+        "Added reward stream {} for mint {} at {} tokens per second",
+        reward_stream.index,
+        reward_stream.reward_mint,
+        reward_per_second
+    );
+
+    // Emit event
+    emit!(RewardStreamAdded {
+        admin: ctx.accounts.admin.key(),
+        reward_stream: reward_stream.key(),
+        index: reward_stream.index,
+        reward_mint: reward_stream.reward_mint,
+        reward_per_second,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,25 +1,37 @@
 use crate::constants::*;
 use crate::errors::StakingError;
 use crate::events::AddedToBlacklist;
-use crate::state::{BlacklistEntry, GlobalState};
+use crate::state::{BlacklistEntry, GlobalState, Role, RoleAssignment};
+use crate::utils::require_blacklist_authority;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 #[instruction(address: Pubkey)]
 pub struct AddToBlacklist<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>, // Pool admin or blacklist manager
 
     #[account(
         seeds = [STATE_SEED, state.staking_mint.as_ref()],
-        bump = state.bump,
-        has_one = admin
+        bump = state.bump
     )]
     pub state: Box<Account<'info, GlobalState>>,
 
+    /// Only required when `authority` is not the pool admin
     #[account(
-        init,
-        payer = admin,
+        seeds = [
+            ROLE_SEED,
+            state.key().as_ref(),
+            &[Role::BlacklistManager as u8],
+            authority.key().as_ref()
+        ],
+        bump = blacklist_manager.bump
+    )]
+    pub blacklist_manager: Option<Box<Account<'info, RoleAssignment>>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
         space = 8 + BlacklistEntry::INIT_SPACE,
         seeds = [BLACKLIST_SEED, state.key().as_ref(), address.as_ref()],
         bump
@@ -29,18 +41,46 @@ pub struct AddToBlacklist<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn add_to_blacklist_handler(ctx: Context<AddToBlacklist>, address: Pubkey) -> Result<()> {
+pub fn add_to_blacklist_handler(
+    ctx: Context<AddToBlacklist>,
+    address: Pubkey,
+    reason_code: u16,
+    reference: Option<[u8; 32]>,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    require_blacklist_authority(
+        &ctx.accounts.state,
+        &ctx.accounts.authority,
+        &ctx.accounts.blacklist_manager,
+    )?;
     require!(
         address != Pubkey::default(),
         StakingError::CannotBlacklistZeroAddress
     );
 
+    let clock = Clock::get()?;
+    if let Some(expires_at) = expires_at {
+        require!(
+            expires_at > clock.unix_timestamp,
+            StakingError::InvalidBlacklistExpiry
+        );
+    }
+
     let blacklist_entry = &mut ctx.accounts.blacklist_entry;
 
-    // Set data for the entry (init constraint ensures this is a new account)
-    let clock = Clock::get()?;
+    // A new account reads as the zero address; an expired entry is overwritten in place
+    let already_listed = blacklist_entry.address != Pubkey::default()
+        && match blacklist_entry.expires_at {
+            Some(previous_expiry) => clock.unix_timestamp < previous_expiry,
+            None => true,
+        };
+    require!(!already_listed, StakingError::AddressAlreadyBlacklisted);
+
     blacklist_entry.address = address;
     blacklist_entry.added_at = clock.unix_timestamp;
+    blacklist_entry.reason_code = reason_code;
+    blacklist_entry.reference = reference;
+    blacklist_entry.expires_at = expires_at;
     blacklist_entry.bump = ctx.bumps.blacklist_entry;
 
     msg!("Added {} to blacklist", address);
@@ -48,7 +88,11 @@ pub fn add_to_blacklist_handler(ctx: Context<AddToBlacklist>, address: Pubkey) -
     // Emit event
     emit!(AddedToBlacklist {
         address,
-        admin: ctx.accounts.admin.key(),
+        admin: ctx.accounts.state.admin,
+        authority: ctx.accounts.authority.key(),
+        reason_code,
+        reference,
+        expires_at,
         timestamp: clock.unix_timestamp,
     });
 
@@ -0,0 +1,126 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::AddedToBlacklist;
+use crate::state::{BlacklistEntry, GlobalState, Role, RoleAssignment};
+use crate::utils::{create_pda_account, is_blacklisted, require_blacklist_authority};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AddToBlacklistBatch<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>, // Pool admin or blacklist manager
+
+    #[account(
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    /// Only required when `authority` is not the pool admin
+    #[account(
+        seeds = [
+            ROLE_SEED,
+            state.key().as_ref(),
+            &[Role::BlacklistManager as u8],
+            authority.key().as_ref()
+        ],
+        bump = blacklist_manager.bump
+    )]
+    pub blacklist_manager: Option<Box<Account<'info, RoleAssignment>>>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: one writable BlacklistEntry PDA per address, in the same order
+}
+
+pub fn add_to_blacklist_batch_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddToBlacklistBatch<'info>>,
+    addresses: Vec<Pubkey>,
+    reason_code: u16,
+    reference: Option<[u8; 32]>,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    require_blacklist_authority(
+        &ctx.accounts.state,
+        &ctx.accounts.authority,
+        &ctx.accounts.blacklist_manager,
+    )?;
+    require!(
+        addresses.len() == ctx.remaining_accounts.len(),
+        StakingError::BlacklistBatchMismatch
+    );
+
+    let clock = Clock::get()?;
+    if let Some(expires_at) = expires_at {
+        require!(
+            expires_at > clock.unix_timestamp,
+            StakingError::InvalidBlacklistExpiry
+        );
+    }
+
+    let state_key = ctx.accounts.state.key();
+
+    for (index, (address, entry_info)) in addresses
+        .iter()
+        .zip(ctx.remaining_accounts.iter())
+        .enumerate()
+    {
+        let (expected_entry, bump) = Pubkey::find_program_address(
+            &[BLACKLIST_SEED, state_key.as_ref(), address.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            entry_info.key(),
+            expected_entry,
+            StakingError::InvalidBlacklistEntry
+        );
+
+        // Skip the zero address, repeats within the batch and addresses still listed
+        let already_listed = is_blacklisted(entry_info, clock.unix_timestamp)?;
+        if *address == Pubkey::default() || addresses[..index].contains(address) || already_listed {
+            continue;
+        }
+
+        // An expired entry is overwritten in place, otherwise the PDA is created
+        let entry_exists = entry_info.owner == &crate::ID && !entry_info.data_is_empty();
+        if !entry_exists {
+            let seeds = &[
+                BLACKLIST_SEED,
+                state_key.as_ref(),
+                address.as_ref(),
+                &[bump],
+            ];
+            create_pda_account(
+                &ctx.accounts.authority,
+                entry_info,
+                &ctx.accounts.system_program,
+                8 + BlacklistEntry::INIT_SPACE,
+                &[&seeds[..]],
+            )?;
+        }
+
+        let entry = BlacklistEntry {
+            address: *address,
+            added_at: clock.unix_timestamp,
+            reason_code,
+            reference,
+            expires_at,
+            bump,
+        };
+        entry.try_serialize(&mut &mut entry_info.try_borrow_mut_data()?[..])?;
+
+        msg!("Added {} to blacklist", address);
+
+        // Emit event
+        emit!(AddedToBlacklist {
+            address: *address,
+            admin: ctx.accounts.state.admin,
+            authority: ctx.accounts.authority.key(),
+            reason_code,
+            reference,
+            expires_at,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
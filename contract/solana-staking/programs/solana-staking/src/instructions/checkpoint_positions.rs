@@ -0,0 +1,70 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::PositionsCheckpointed;
+use crate::state::{GlobalState, UserStakeInfo};
+use crate::utils::{accrue_pending_rewards, checkpoint_user, update_reward_index};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CheckpointPositions<'info> {
+    pub caller: Signer<'info>, // Anyone may crank
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+    // remaining_accounts: writable UserStakeInfo PDAs of this pool
+}
+
+pub fn checkpoint_positions_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CheckpointPositions<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let state = &mut ctx.accounts.state;
+    let state_key = state.key();
+
+    update_reward_index(state, clock.unix_timestamp)?;
+
+    for account_info in ctx.remaining_accounts.iter() {
+        require!(account_info.is_writable, StakingError::InvalidStakePosition);
+        let mut user_stake = Account::<UserStakeInfo>::try_from(account_info)?;
+        let expected_stake = Pubkey::create_program_address(
+            &[
+                STAKE_SEED,
+                state_key.as_ref(),
+                user_stake.owner.as_ref(),
+                &[user_stake.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| StakingError::InvalidStakePosition)?;
+        require_keys_eq!(
+            account_info.key(),
+            expected_stake,
+            StakingError::InvalidStakePosition
+        );
+
+        // Rewards so far are recorded as unpaid; nothing is paid out here
+        accrue_pending_rewards(state, &mut user_stake, clock.unix_timestamp)?;
+        checkpoint_user(state, &mut user_stake, clock.unix_timestamp)?;
+        user_stake.exit(&crate::ID)?;
+    }
+
+    msg!(
+        "Checkpointed {} positions, {} stale boosted positions left",
+        ctx.remaining_accounts.len(),
+        state.stale_boosted_positions
+    );
+
+    // Emit event
+    emit!(PositionsCheckpointed {
+        caller: ctx.accounts.caller.key(),
+        count: ctx.remaining_accounts.len() as u32,
+        stale_boosted_positions: state.stale_boosted_positions,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -2,61 +2,102 @@ use crate::constants::*;
 use crate::errors::StakingError;
 use crate::events::RewardsClaimed;
 use crate::state::{GlobalState, UserStakeInfo};
-use crate::utils::claim_pending_rewards;
+use crate::utils::{claim_pending_rewards, is_blacklisted};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct ClaimRewards<'info> {
-    #[account(mut)]
+    /// The staker or their claim delegate
     pub user: Signer<'info>,
 
     #[account(
+        mut,
         seeds = [STATE_SEED, state.staking_mint.as_ref()],
-        bump = state.bump
+        bump = state.bump,
+        has_one = reward_mint
     )]
     pub state: Box<Account<'info, GlobalState>>,
 
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
     #[account(
         mut,
-        seeds = [STAKE_SEED, state.key().as_ref(), user.key().as_ref()],
-        bump = user_stake_info.bump
+        seeds = [STAKE_SEED, state.key().as_ref(), user_stake_info.owner.as_ref()],
+        bump = user_stake_info.bump,
+        constraint = user.key() == user_stake_info.owner
+            || user_stake_info.claim_delegate == Some(user.key())
+            @ StakingError::NotClaimAuthority
     )]
     pub user_stake_info: Box<Account<'info, UserStakeInfo>>,
 
+    /// Must belong to the registered reward recipient, or to the owner when none is set
     #[account(
         mut,
         token::mint = state.reward_mint,
-        token::authority = user
+        constraint = user_reward_account.owner
+            == user_stake_info.reward_recipient.unwrap_or(user_stake_info.owner)
+            @ StakingError::InvalidRewardRecipient
     )]
-    pub user_reward_account: Account<'info, TokenAccount>,
+    pub user_reward_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         seeds = [REWARD_VAULT_SEED, state.key().as_ref()],
         bump
     )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
 
-    /// CHECK: This account may or may not exist - we check if it exists to determine blacklist status
+    /// CHECK: This account may or may not exist - `is_blacklisted` checks its owner and discriminator
     #[account(
-        seeds = [BLACKLIST_SEED, state.key().as_ref(), user.key().as_ref()],
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), user_stake_info.owner.as_ref()],
         bump,
     )]
     pub blacklist_entry: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: The recipient's entry (the owner's again when no recipient is set) - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), user_reward_account.owner.as_ref()],
+        bump,
+    )]
+    pub recipient_blacklist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: The signer's entry (the owner's again when they claim themselves) - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub delegate_blacklist_entry: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub clock: Sysvar<'info, Clock>,
 }
 
-pub fn claim_rewards_handler(ctx: Context<ClaimRewards>) -> Result<()> {
-    let blacklist_info = &ctx.accounts.blacklist_entry.to_account_info();
+pub fn claim_rewards_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimRewards<'info>>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.state.paused & PAUSE_CLAIM == 0,
+        StakingError::OperationPaused
+    );
+
+    // Rewards may neither be claimed by nor paid to a blacklisted address, and a
+    // blacklisted delegate may not claim on the owner's behalf
+    let now = ctx.accounts.clock.unix_timestamp;
+    require!(
+        !is_blacklisted(&ctx.accounts.blacklist_entry, now)?,
+        StakingError::AddressBlacklisted
+    );
+    require!(
+        !is_blacklisted(&ctx.accounts.delegate_blacklist_entry, now)?,
+        StakingError::AddressBlacklisted
+    );
     require!(
-        blacklist_info.data_is_empty() || blacklist_info.lamports() == 0,
+        !is_blacklisted(&ctx.accounts.recipient_blacklist_entry, now)?,
         StakingError::AddressBlacklisted
     );
 
-    let state = &ctx.accounts.state;
+    let state = &mut ctx.accounts.state;
     let user_stake = &mut ctx.accounts.user_stake_info;
     let clock = &ctx.accounts.clock;
 
@@ -64,21 +105,25 @@ pub fn claim_rewards_handler(ctx: Context<ClaimRewards>) -> Result<()> {
         state,
         user_stake,
         &ctx.accounts.reward_vault,
+        &ctx.accounts.reward_mint,
         &ctx.accounts.user_reward_account,
         &ctx.accounts.token_program,
+        ctx.remaining_accounts,
         clock,
     )?;
 
     if rewards > 0 {
         msg!(
-            "User {} claimed {} rewards",
-            ctx.accounts.user.key(),
-            rewards
+            "User {} claimed {} rewards to {}",
+            user_stake.owner,
+            rewards,
+            ctx.accounts.user_reward_account.owner
         );
 
         // Emit rewards claimed event
         emit!(RewardsClaimed {
-            user: ctx.accounts.user.key(),
+            user: user_stake.owner,
+            recipient: ctx.accounts.user_reward_account.owner,
             amount: rewards,
             timestamp: clock.unix_timestamp,
         });
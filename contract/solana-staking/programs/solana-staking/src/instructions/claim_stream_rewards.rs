@@ -0,0 +1,152 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::StreamRewardsClaimed;
+use crate::state::{GlobalState, RewardStream, UserStakeInfo};
+use crate::utils::{is_blacklisted, settle_reward_stream, transfer_checked_with_hook};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ClaimStreamRewards<'info> {
+    /// The staker or their claim delegate
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, state.key().as_ref(), user_stake_info.owner.as_ref()],
+        bump = user_stake_info.bump,
+        constraint = user.key() == user_stake_info.owner
+            || user_stake_info.claim_delegate == Some(user.key())
+            @ StakingError::NotClaimAuthority
+    )]
+    pub user_stake_info: Box<Account<'info, UserStakeInfo>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD_STREAM_SEED, state.key().as_ref(), &[reward_stream.index]],
+        bump = reward_stream.bump,
+        has_one = state
+    )]
+    pub reward_stream: Box<Account<'info, RewardStream>>,
+
+    #[account(address = reward_stream.reward_mint)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [STREAM_VAULT_SEED, reward_stream.key().as_ref()],
+        bump
+    )]
+    pub stream_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Must belong to the registered reward recipient, or to the owner when none is set
+    #[account(
+        mut,
+        token::mint = reward_stream.reward_mint,
+        constraint = user_reward_account.owner
+            == user_stake_info.reward_recipient.unwrap_or(user_stake_info.owner)
+            @ StakingError::InvalidRewardRecipient
+    )]
+    pub user_reward_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: This account may or may not exist - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), user_stake_info.owner.as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: The recipient's entry (the owner's again when no recipient is set) - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), user_reward_account.owner.as_ref()],
+        bump,
+    )]
+    pub recipient_blacklist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: The signer's entry (the owner's again when they claim themselves) - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub delegate_blacklist_entry: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn claim_stream_rewards_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimStreamRewards<'info>>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.state.paused & PAUSE_CLAIM == 0,
+        StakingError::OperationPaused
+    );
+
+    // Same rules as claim_rewards: no blacklisted owner, recipient or delegate
+    let clock = Clock::get()?;
+    for blacklist_entry in [
+        &ctx.accounts.blacklist_entry,
+        &ctx.accounts.recipient_blacklist_entry,
+        &ctx.accounts.delegate_blacklist_entry,
+    ] {
+        require!(
+            !is_blacklisted(blacklist_entry, clock.unix_timestamp)?,
+            StakingError::AddressBlacklisted
+        );
+    }
+
+    let state = &ctx.accounts.state;
+    let reward_stream = &mut ctx.accounts.reward_stream;
+    let user_stake = &mut ctx.accounts.user_stake_info;
+
+    settle_reward_stream(state, reward_stream, user_stake, clock.unix_timestamp)?;
+
+    let checkpoint = &mut user_stake.stream_rewards[reward_stream.index as usize];
+    let rewards = checkpoint.unclaimed;
+
+    if rewards > 0 {
+        checkpoint.unclaimed = 0;
+
+        // Transfer rewards from stream vault to user
+        let seeds = &[STATE_SEED, state.staking_mint.as_ref(), &[state.bump]];
+        let signer = &[&seeds[..]];
+
+        transfer_checked_with_hook(
+            &ctx.accounts.token_program,
+            ctx.accounts.stream_vault.to_account_info(),
+            &ctx.accounts.reward_mint,
+            ctx.accounts.user_reward_account.to_account_info(),
+            state.to_account_info(),
+            ctx.remaining_accounts,
+            rewards,
+            signer,
+        )?;
+
+        msg!(
+            "User {} claimed {} rewards from stream {} to {}",
+            user_stake.owner,
+            rewards,
+            reward_stream.index,
+            ctx.accounts.user_reward_account.owner
+        );
+
+        // Emit stream rewards claimed event
+        emit!(StreamRewardsClaimed {
+            user: user_stake.owner,
+            reward_stream: reward_stream.key(),
+            reward_mint: reward_stream.reward_mint,
+            amount: rewards,
+            timestamp: clock.unix_timestamp,
+        });
+    } else {
+        msg!("No stream rewards to claim");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::StakeAccountClosed;
+use crate::state::{GlobalState, UserStakeInfo};
+use crate::utils::pending_rewards;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CloseStakeAccount<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [STAKE_SEED, state.key().as_ref(), user.key().as_ref()],
+        bump = user_stake_info.bump
+    )]
+    pub user_stake_info: Box<Account<'info, UserStakeInfo>>,
+}
+
+pub fn close_stake_account_handler(ctx: Context<CloseStakeAccount>) -> Result<()> {
+    let user_stake = &ctx.accounts.user_stake_info;
+    let clock = Clock::get()?;
+
+    // Anything still owed to the user would be lost with the account
+    require!(
+        user_stake.amount == 0
+            && user_stake.unbonding_amount == 0
+            && user_stake.unpaid_rewards == 0
+            && pending_rewards(&ctx.accounts.state, user_stake, clock.unix_timestamp)? == 0
+            && user_stake
+                .stream_rewards
+                .iter()
+                .all(|checkpoint| checkpoint.unclaimed == 0),
+        StakingError::StakeAccountNotEmpty
+    );
+
+    msg!("Closed stake account of {}", ctx.accounts.user.key());
+
+    // Emit event
+    emit!(StakeAccountClosed {
+        user: ctx.accounts.user.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
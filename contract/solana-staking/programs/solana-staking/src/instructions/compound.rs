@@ -0,0 +1,175 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::Compounded;
+use crate::state::{GlobalState, UserStakeInfo};
+use crate::utils::{
+    checkpoint_reward_streams, checkpoint_user, claim_pending_rewards, is_blacklisted,
+    load_reward_streams, mint_receipt, settle_reward_streams, update_reward_index,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct Compound<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = staking_mint
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    pub staking_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, state.key().as_ref(), user.key().as_ref()],
+        bump = user_stake_info.bump
+    )]
+    pub user_stake_info: Box<Account<'info, UserStakeInfo>>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_VAULT_SEED, state.key().as_ref()],
+        bump
+    )]
+    pub staking_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_SEED, state.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: This account may or may not exist - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Only required when the pool has a receipt mint
+    #[account(mut, address = state.receipt_mint)]
+    pub receipt_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        token::mint = state.receipt_mint,
+        token::authority = user
+    )]
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub receipt_token_program: Option<Program<'info, Token2022>>,
+}
+
+pub fn compound_handler<'info>(ctx: Context<'_, '_, 'info, 'info, Compound<'info>>) -> Result<()> {
+    require!(
+        ctx.accounts.state.reward_mint == ctx.accounts.state.staking_mint,
+        StakingError::CompoundUnavailable
+    );
+    require!(
+        ctx.accounts.state.paused & (PAUSE_STAKE | PAUSE_CLAIM) == 0,
+        StakingError::OperationPaused
+    );
+
+    require!(
+        !is_blacklisted(
+            &ctx.accounts.blacklist_entry,
+            ctx.accounts.clock.unix_timestamp
+        )?,
+        StakingError::AddressBlacklisted
+    );
+
+    // Compounding keeps rewards with the owner, which a registered recipient rules out
+    let user_stake = &ctx.accounts.user_stake_info;
+    require!(
+        user_stake
+            .reward_recipient
+            .filter(|recipient| *recipient != user_stake.owner)
+            .is_none(),
+        StakingError::InvalidRewardRecipient
+    );
+
+    let state = &mut ctx.accounts.state;
+    let user_stake = &mut ctx.accounts.user_stake_info;
+    let clock = &ctx.accounts.clock;
+
+    // Checkpoint global and per-stream accrual before total_staked changes
+    update_reward_index(state, clock.unix_timestamp)?;
+    let mut reward_streams = load_reward_streams(state, ctx.remaining_accounts)?;
+    settle_reward_streams(state, &mut reward_streams, user_stake, clock.unix_timestamp)?;
+
+    // Pay pending rewards straight into the staking vault instead of the user's wallet
+    let vault_balance_before = ctx.accounts.staking_vault.amount;
+    let rewards = claim_pending_rewards(
+        state,
+        user_stake,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.staking_mint,
+        &ctx.accounts.staking_vault,
+        &ctx.accounts.token_program,
+        ctx.remaining_accounts,
+        clock,
+    )?;
+
+    if rewards == 0 {
+        msg!("No rewards to compound");
+        checkpoint_reward_streams(&reward_streams, user_stake)?;
+        return Ok(());
+    }
+
+    // Only what reached the vault is staked; transfer-fee mints withhold part of it
+    ctx.accounts.staking_vault.reload()?;
+    let rewards = ctx
+        .accounts
+        .staking_vault
+        .amount
+        .checked_sub(vault_balance_before)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    mint_receipt(
+        state,
+        ctx.accounts.receipt_mint.as_deref(),
+        ctx.accounts.user_receipt_account.as_deref(),
+        ctx.accounts.receipt_token_program.as_ref(),
+        rewards,
+    )?;
+
+    // Update user stake info
+    user_stake.amount = user_stake
+        .amount
+        .checked_add(rewards)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    checkpoint_user(state, user_stake, clock.unix_timestamp)?;
+    checkpoint_reward_streams(&reward_streams, user_stake)?;
+
+    // Update global state
+    state.total_staked = state
+        .total_staked
+        .checked_add(rewards)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    msg!(
+        "User {} compounded {} rewards",
+        ctx.accounts.user.key(),
+        rewards
+    );
+
+    // Emit compounded event
+    emit!(Compounded {
+        user: ctx.accounts.user.key(),
+        amount: rewards,
+        new_stake: user_stake.amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
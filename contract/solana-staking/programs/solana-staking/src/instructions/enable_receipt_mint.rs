@@ -0,0 +1,135 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::ReceiptMintEnabled;
+use crate::state::GlobalState;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::Mint;
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+#[derive(Accounts)]
+pub struct EnableReceiptMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = admin,
+        has_one = staking_mint
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    pub staking_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token-2022 mint whose transfer hook is this program, so receipts carry their
+    /// stake with them
+    #[account(
+        init,
+        payer = admin,
+        mint::decimals = staking_mint.decimals,
+        mint::authority = state,
+        mint::token_program = token_program,
+        extensions::transfer_hook::authority = state,
+        extensions::transfer_hook::program_id = crate::ID,
+        seeds = [RECEIPT_MINT_SEED, state.key().as_ref()],
+        bump
+    )]
+    pub receipt_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: TLV list written by the handler
+    #[account(
+        init,
+        payer = admin,
+        space = ExtraAccountMetaList::size_of(RECEIPT_HOOK_EXTRA_ACCOUNT_COUNT)?,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, receipt_mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Extra accounts Token-2022 resolves for every receipt transfer. Indices 0-4 are the
+/// source token account, mint, destination token account, authority and this list.
+pub fn receipt_hook_account_metas(state: &Pubkey) -> Result<Vec<ExtraAccountMeta>> {
+    let owner_pda = |literal: &[u8], token_account_index: u8, is_writable: bool| {
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: literal.to_vec(),
+                },
+                Seed::AccountKey { index: 5 },
+                Seed::AccountData {
+                    account_index: token_account_index,
+                    data_index: TOKEN_ACCOUNT_OWNER_OFFSET,
+                    length: 32,
+                },
+            ],
+            false,
+            is_writable,
+        )
+    };
+
+    let mut metas = vec![
+        // 5: pool state
+        ExtraAccountMeta::new_with_pubkey(state, false, true)?,
+        // 6-7: source and destination owners' stake positions
+        owner_pda(STAKE_SEED, 0, true)?,
+        owner_pda(STAKE_SEED, 2, true)?,
+        // 8-9: source and destination owners' blacklist entries
+        owner_pda(BLACKLIST_SEED, 0, false)?,
+        owner_pda(BLACKLIST_SEED, 2, false)?,
+    ];
+    // 10+: every stream slot, registered or not, so later streams need no list update
+    for index in 0..MAX_REWARD_STREAMS as u8 {
+        metas.push(ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: REWARD_STREAM_SEED.to_vec(),
+                },
+                Seed::AccountKey { index: 5 },
+                Seed::Literal { bytes: vec![index] },
+            ],
+            false,
+            true,
+        )?);
+    }
+
+    Ok(metas)
+}
+
+pub fn enable_receipt_mint_handler(ctx: Context<EnableReceiptMint>) -> Result<()> {
+    // Every active position must be backed by receipts from the start
+    require!(
+        ctx.accounts.state.total_staked == 0,
+        StakingError::ReceiptMintUnavailable
+    );
+
+    let extra_account_metas = receipt_hook_account_metas(&ctx.accounts.state.key())?;
+    let account_info = ctx.accounts.extra_account_meta_list.to_account_info();
+    let mut data = account_info.try_borrow_mut_data()?;
+    ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &extra_account_metas)?;
+
+    let state = &mut ctx.accounts.state;
+    state.receipt_mint = ctx.accounts.receipt_mint.key();
+
+    let clock = Clock::get()?;
+
+    msg!("Receipt mint enabled: {}", state.receipt_mint);
+
+    // Emit event
+    emit!(ReceiptMintEnabled {
+        admin: ctx.accounts.admin.key(),
+        receipt_mint: state.receipt_mint,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::{ForceUnstaked, StreamRewardsCancelled};
+use crate::state::{GlobalState, UserStakeInfo};
+use crate::utils::{
+    checkpoint_reward_streams, checkpoint_user, claim_pending_rewards, is_blacklisted,
+    load_reward_streams, owed_rewards, reset_empty_position, settle_reward_streams,
+    transfer_checked_with_hook, update_reward_index,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct ForceUnstake<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = admin,
+        has_one = staking_mint,
+        has_one = reward_mint
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    pub staking_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, state.key().as_ref(), user.as_ref()],
+        bump = user_stake_info.bump
+    )]
+    pub user_stake_info: Box<Account<'info, UserStakeInfo>>,
+
+    /// CHECK: This account may or may not exist - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), user.as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_VAULT_SEED, state.key().as_ref()],
+        bump
+    )]
+    pub staking_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_SEED, state.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = state.staking_mint,
+        token::authority = state.quarantine
+    )]
+    pub quarantine_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Only required when pending rewards are not cancelled
+    #[account(
+        mut,
+        token::mint = state.reward_mint,
+        token::authority = state.quarantine
+    )]
+    pub quarantine_reward_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn force_unstake_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ForceUnstake<'info>>,
+    user: Pubkey,
+    cancel_rewards: bool,
+) -> Result<()> {
+    require!(
+        ctx.accounts.state.quarantine != Pubkey::default(),
+        StakingError::QuarantineNotSet
+    );
+    // Seizing principal would leave the user's receipts unbacked
+    require!(
+        ctx.accounts.state.receipt_mint == Pubkey::default(),
+        StakingError::ForceUnstakeUnavailable
+    );
+    require!(
+        is_blacklisted(
+            &ctx.accounts.blacklist_entry,
+            ctx.accounts.clock.unix_timestamp
+        )?,
+        StakingError::AddressNotBlacklisted
+    );
+
+    let state = &mut ctx.accounts.state;
+    let user_stake = &mut ctx.accounts.user_stake_info;
+    let clock = &ctx.accounts.clock;
+
+    let amount = user_stake.amount;
+    let unbonding_amount = user_stake.unbonding_amount;
+    let seized = amount
+        .checked_add(unbonding_amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    require!(seized > 0, StakingError::NothingToForceUnstake);
+
+    // Checkpoint global and per-stream accrual before total_staked changes
+    update_reward_index(state, clock.unix_timestamp)?;
+    let mut reward_streams = load_reward_streams(state, ctx.remaining_accounts)?;
+    settle_reward_streams(state, &mut reward_streams, user_stake, clock.unix_timestamp)?;
+
+    // Cancelled rewards stay in their vaults; otherwise primary rewards are quarantined
+    // too and stream rewards stay claimable by the user should they be delisted
+    let mut rewards_paid = 0;
+    let rewards_cancelled;
+    let mut stream_rewards_cancelled = Vec::new();
+    if cancel_rewards {
+        rewards_cancelled = owed_rewards(state, user_stake, clock.unix_timestamp)?;
+        user_stake.unpaid_rewards = 0;
+        for stream in reward_streams.iter() {
+            let checkpoint = &mut user_stake.stream_rewards[stream.index as usize];
+            if checkpoint.unclaimed > 0 {
+                stream_rewards_cancelled.push(StreamRewardsCancelled {
+                    reward_mint: stream.reward_mint,
+                    amount: checkpoint.unclaimed,
+                });
+            }
+            checkpoint.unclaimed = 0;
+        }
+    } else {
+        let quarantine_reward_account = ctx
+            .accounts
+            .quarantine_reward_account
+            .as_deref()
+            .ok_or(StakingError::MissingQuarantineRewardAccount)?;
+        rewards_paid = claim_pending_rewards(
+            state,
+            user_stake,
+            &ctx.accounts.reward_vault,
+            &ctx.accounts.reward_mint,
+            quarantine_reward_account,
+            &ctx.accounts.token_program,
+            ctx.remaining_accounts,
+            clock,
+        )?;
+
+        // Whatever the vault could not cover (all of it while claims are paused) is
+        // cancelled rather than left owed to a wallet that no longer has a position
+        rewards_cancelled = user_stake.unpaid_rewards;
+        user_stake.unpaid_rewards = 0;
+    }
+
+    // Transfer the whole position to the quarantine account
+    let seeds = &[STATE_SEED, state.staking_mint.as_ref(), &[state.bump]];
+    let signer = &[&seeds[..]];
+
+    transfer_checked_with_hook(
+        &ctx.accounts.token_program,
+        ctx.accounts.staking_vault.to_account_info(),
+        &ctx.accounts.staking_mint,
+        ctx.accounts.quarantine_token_account.to_account_info(),
+        state.to_account_info(),
+        ctx.remaining_accounts,
+        seized,
+        signer,
+    )?;
+
+    // Zero the position; the lock, any pending withdrawal, the claim settings and the
+    // allowlist admission go with it. Only unclaimed stream rewards that were not
+    // cancelled and total_claimed survive
+    user_stake.amount = 0;
+    user_stake.unbonding_amount = 0;
+    user_stake.unbonding_available_at = 0;
+    user_stake.lock_until = 0;
+    user_stake.multiplier_bps = BPS_DENOMINATOR;
+    user_stake.reward_recipient = None;
+    user_stake.claim_delegate = None;
+    user_stake.allowlist_verified = false;
+    checkpoint_user(state, user_stake, clock.unix_timestamp)?;
+    checkpoint_reward_streams(&reward_streams, user_stake)?;
+    reset_empty_position(user_stake);
+
+    // Unbonding tokens already left total_staked when they were requested
+    state.total_staked = state
+        .total_staked
+        .checked_sub(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    msg!(
+        "Force unstaked {} tokens ({} unbonding) from {} into quarantine",
+        amount,
+        unbonding_amount,
+        user
+    );
+
+    // Emit event
+    emit!(ForceUnstaked {
+        admin: ctx.accounts.admin.key(),
+        user,
+        amount,
+        unbonding_amount,
+        quarantine_account: ctx.accounts.quarantine_token_account.key(),
+        rewards_paid,
+        rewards_cancelled,
+        stream_rewards_cancelled,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::RewardsFunded;
+use crate::state::GlobalState;
+use crate::utils::{reward_runway_seconds, transfer_checked_with_hook};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = reward_mint
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = state.reward_mint,
+        token::authority = funder
+    )]
+    pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_SEED, state.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn fund_rewards_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FundRewards<'info>>,
+    amount: u64,
+    compute_runway: bool,
+) -> Result<()> {
+    require!(amount > 0, StakingError::InvalidFundAmount);
+
+    let vault_balance_before = ctx.accounts.reward_vault.amount;
+    transfer_checked_with_hook(
+        &ctx.accounts.token_program,
+        ctx.accounts.funder_token_account.to_account_info(),
+        &ctx.accounts.reward_mint,
+        ctx.accounts.reward_vault.to_account_info(),
+        ctx.accounts.funder.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        &[],
+    )?;
+
+    // Count what the vault actually received; transfer-fee mints withhold part of it
+    ctx.accounts.reward_vault.reload()?;
+    let vault_balance = ctx.accounts.reward_vault.amount;
+    let amount = vault_balance
+        .checked_sub(vault_balance_before)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    let state = &mut ctx.accounts.state;
+    state.total_funded = state
+        .total_funded
+        .checked_add(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    let runway_seconds = if compute_runway {
+        reward_runway_seconds(state, vault_balance)
+    } else {
+        None
+    };
+
+    let clock = Clock::get()?;
+
+    msg!(
+        "{} funded {} reward tokens, vault now holds {}",
+        ctx.accounts.funder.key(),
+        amount,
+        vault_balance
+    );
+
+    // Emit event
+    emit!(RewardsFunded {
+        funder: ctx.accounts.funder.key(),
+        amount,
+        total_funded: state.total_funded,
+        vault_balance,
+        runway_seconds,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
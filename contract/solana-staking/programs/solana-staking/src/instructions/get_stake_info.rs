@@ -0,0 +1,49 @@
+use crate::constants::*;
+use crate::state::GlobalState;
+use crate::utils::{load_user_stake, owed_rewards};
+use anchor_lang::prelude::*;
+
+/// Returned by `get_stake_info`; all zeros for a wallet that never staked.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct StakeInfoView {
+    pub amount: u64,
+    pub stake_timestamp: i64,
+    pub pending_rewards: u64, // Includes unpaid_rewards
+    pub total_claimed: u64,
+    pub unbonding_amount: u64,
+    pub lock_until: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct GetStakeInfo<'info> {
+    #[account(
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    /// CHECK: May not exist yet - `load_user_stake` checks its owner and discriminator
+    #[account(
+        seeds = [STAKE_SEED, state.key().as_ref(), user.as_ref()],
+        bump
+    )]
+    pub user_stake_info: UncheckedAccount<'info>,
+}
+
+pub fn get_stake_info_handler(ctx: Context<GetStakeInfo>, _user: Pubkey) -> Result<StakeInfoView> {
+    let Some(user_stake) = load_user_stake(&ctx.accounts.user_stake_info)? else {
+        return Ok(StakeInfoView::default());
+    };
+
+    let clock = Clock::get()?;
+
+    Ok(StakeInfoView {
+        amount: user_stake.amount,
+        stake_timestamp: user_stake.stake_timestamp,
+        pending_rewards: owed_rewards(&ctx.accounts.state, &user_stake, clock.unix_timestamp)?,
+        total_claimed: user_stake.total_claimed,
+        unbonding_amount: user_stake.unbonding_amount,
+        lock_until: user_stake.lock_until,
+    })
+}
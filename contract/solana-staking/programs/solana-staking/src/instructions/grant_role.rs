@@ -0,0 +1,52 @@
+use crate::constants::*;
+use crate::events::RoleGranted;
+use crate::state::{GlobalState, Role, RoleAssignment};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(role: Role, member: Pubkey)]
+pub struct GrantRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = admin
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RoleAssignment::INIT_SPACE,
+        seeds = [ROLE_SEED, state.key().as_ref(), &[role as u8], member.as_ref()],
+        bump
+    )]
+    pub role_assignment: Box<Account<'info, RoleAssignment>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn grant_role_handler(ctx: Context<GrantRole>, role: Role, member: Pubkey) -> Result<()> {
+    let role_assignment = &mut ctx.accounts.role_assignment;
+
+    let clock = Clock::get()?;
+    role_assignment.state = ctx.accounts.state.key();
+    role_assignment.role = role;
+    role_assignment.member = member;
+    role_assignment.granted_at = clock.unix_timestamp;
+    role_assignment.bump = ctx.bumps.role_assignment;
+
+    msg!("Granted {:?} to {}", role, member);
+
+    // Emit event
+    emit!(RoleGranted {
+        admin: ctx.accounts.admin.key(),
+        role,
+        member,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
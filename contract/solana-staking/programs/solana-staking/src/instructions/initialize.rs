@@ -1,8 +1,9 @@
 use crate::constants::*;
 use crate::events::Initialized;
-use crate::state::GlobalState;
+use crate::state::{GlobalState, RewardMode};
+use crate::utils::validate_mint_extensions;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -18,8 +19,11 @@ pub struct Initialize<'info> {
     )]
     pub state: Box<Account<'info, GlobalState>>,
 
-    pub staking_mint: Account<'info, Mint>,
-    pub reward_mint: Account<'info, Mint>,
+    // Both mints must belong to the same token program, which the pool then uses for every CPI
+    #[account(mint::token_program = token_program)]
+    pub staking_mint: InterfaceAccount<'info, Mint>,
+    #[account(mint::token_program = token_program)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init,
@@ -29,7 +33,7 @@ pub struct Initialize<'info> {
         seeds = [STAKING_VAULT_SEED, state.key().as_ref()],
         bump
     )]
-    pub staking_vault: Account<'info, TokenAccount>,
+    pub staking_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init,
@@ -39,41 +43,90 @@ pub struct Initialize<'info> {
         seeds = [REWARD_VAULT_SEED, state.key().as_ref()],
         bump
     )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub clock: Sysvar<'info, Clock>,
 }
 
-pub fn initialize_handler(ctx: Context<Initialize>, reward_rate: u64) -> Result<()> {
-    require!(
-        reward_rate > 0 && reward_rate <= 1000,
-        crate::errors::StakingError::InvalidRewardRate
-    );
+pub fn initialize_handler(
+    ctx: Context<Initialize>,
+    reward_rate: u64,
+    reward_mode: RewardMode,
+    reward_per_second: u64,
+) -> Result<()> {
+    match reward_mode {
+        RewardMode::FixedRate => {
+            require!(
+                reward_rate > 0 && reward_rate <= 1000,
+                crate::errors::StakingError::InvalidRewardRate
+            );
+            require!(
+                reward_per_second == 0,
+                crate::errors::StakingError::InvalidRewardPerSecond
+            );
+        }
+        RewardMode::Accumulator => {
+            require!(
+                reward_per_second > 0,
+                crate::errors::StakingError::InvalidRewardPerSecond
+            );
+            require!(
+                reward_rate == 0,
+                crate::errors::StakingError::InvalidRewardRate
+            );
+        }
+    }
+
+    validate_mint_extensions(&ctx.accounts.staking_mint)?;
+    validate_mint_extensions(&ctx.accounts.reward_mint)?;
 
     let state = &mut ctx.accounts.state;
 
     state.admin = ctx.accounts.admin.key();
+    state.pending_admin = Pubkey::default();
     state.staking_mint = ctx.accounts.staking_mint.key();
     state.reward_mint = ctx.accounts.reward_mint.key();
     state.staking_vault = ctx.accounts.staking_vault.key();
     state.reward_vault = ctx.accounts.reward_vault.key();
+    state.reward_mode = reward_mode;
     state.reward_rate = reward_rate;
+    state.reward_per_second = reward_per_second;
     state.total_staked = 0;
+    state.reward_index = 0;
+    state.acc_reward_per_share = 0;
+    state.last_update_time = ctx.accounts.clock.unix_timestamp;
+    // Open-ended campaign until the admin sets one
+    state.rewards_start = ctx.accounts.clock.unix_timestamp;
+    state.rewards_end = i64::MAX;
+    state.epoch_start_time = ctx.accounts.clock.unix_timestamp;
+    state.paused = 0;
+    state.reward_stream_count = 0;
+    state.lock_tier_count = 0;
+    state.cooldown_seconds = 0;
+    state.receipt_mint = Pubkey::default();
     state.bump = ctx.bumps.state;
 
-    msg!(
-        "Staking program initialized with reward rate: {}%",
-        reward_rate as f64 / 100.0
-    );
+    match reward_mode {
+        RewardMode::FixedRate => msg!(
+            "Staking program initialized with reward rate: {}%",
+            reward_rate as f64 / 100.0
+        ),
+        RewardMode::Accumulator => msg!(
+            "Staking program initialized with {} reward tokens per second",
+            reward_per_second
+        ),
+    }
 
     // Emit initialized event
     emit!(Initialized {
         authority: ctx.accounts.admin.key(),
         staking_mint: ctx.accounts.staking_mint.key(),
         reward_mint: ctx.accounts.reward_mint.key(),
+        reward_mode,
         reward_rate,
+        reward_per_second,
         timestamp: ctx.accounts.clock.unix_timestamp,
     });
 
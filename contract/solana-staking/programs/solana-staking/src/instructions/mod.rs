@@ -1,13 +1,77 @@
+pub mod accept_admin;
+pub mod add_reward_stream;
 pub mod add_to_blacklist;
+pub mod add_to_blacklist_batch;
+pub mod cancel_unstake;
+pub mod checkpoint_positions;
 pub mod claim_rewards;
+pub mod claim_stream_rewards;
+pub mod close_stake_account;
+pub mod compound;
+pub mod enable_receipt_mint;
+pub mod force_unstake;
+pub mod fund_rewards;
+pub mod get_stake_info;
+pub mod grant_role;
 pub mod initialize;
+pub mod open_stake_account;
+pub mod pause;
+pub mod pending_rewards;
+pub mod propose_admin;
+pub mod receipt_transfer_hook;
 pub mod remove_from_blacklist;
+pub mod remove_from_blacklist_batch;
+pub mod request_unstake;
+pub mod revoke_role;
+pub mod set_allowlist_root;
+pub mod set_claim_settings;
+pub mod set_cooldown;
+pub mod set_lock_tiers;
+pub mod set_quarantine;
+pub mod set_reward_campaign;
 pub mod stake;
+pub mod stake_for;
+pub mod unpause;
 pub mod unstake;
+pub mod update_reward_rate;
+pub mod withdraw;
+pub mod withdraw_reward_tokens;
 
+pub use accept_admin::*;
+pub use add_reward_stream::*;
 pub use add_to_blacklist::*;
+pub use add_to_blacklist_batch::*;
+pub use cancel_unstake::*;
+pub use checkpoint_positions::*;
 pub use claim_rewards::*;
+pub use claim_stream_rewards::*;
+pub use close_stake_account::*;
+pub use compound::*;
+pub use enable_receipt_mint::*;
+pub use force_unstake::*;
+pub use fund_rewards::*;
+pub use get_stake_info::*;
+pub use grant_role::*;
 pub use initialize::*;
+pub use open_stake_account::*;
+pub use pause::*;
+pub use pending_rewards::*;
+pub use propose_admin::*;
+pub use receipt_transfer_hook::*;
 pub use remove_from_blacklist::*;
+pub use remove_from_blacklist_batch::*;
+pub use request_unstake::*;
+pub use revoke_role::*;
+pub use set_allowlist_root::*;
+pub use set_claim_settings::*;
+pub use set_cooldown::*;
+pub use set_lock_tiers::*;
+pub use set_quarantine::*;
+pub use set_reward_campaign::*;
 pub use stake::*;
+pub use stake_for::*;
+pub use unpause::*;
 pub use unstake::*;
+pub use update_reward_rate::*;
+pub use withdraw::*;
+pub use withdraw_reward_tokens::*;
@@ -0,0 +1,51 @@
+use crate::constants::*;
+use crate::events::StakeAccountOpened;
+use crate::state::{GlobalState, UserStakeInfo};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct OpenStakeAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Future position owner; only used as a PDA seed
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + UserStakeInfo::INIT_SPACE,
+        seeds = [STAKE_SEED, state.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub user_stake_info: Box<Account<'info, UserStakeInfo>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates an empty position so `owner` can receive receipt tokens; the receipt hook
+/// cannot create accounts itself.
+pub fn open_stake_account_handler(ctx: Context<OpenStakeAccount>) -> Result<()> {
+    let user_stake = &mut ctx.accounts.user_stake_info;
+    user_stake.owner = ctx.accounts.owner.key();
+    user_stake.bump = ctx.bumps.user_stake_info;
+
+    let clock = Clock::get()?;
+
+    msg!("Stake account opened for {}", ctx.accounts.owner.key());
+
+    // Emit event
+    emit!(StakeAccountOpened {
+        payer: ctx.accounts.payer.key(),
+        owner: ctx.accounts.owner.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::constants::*;
+use crate::state::GlobalState;
+use crate::utils::{load_user_stake, owed_rewards};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct PendingRewards<'info> {
+    #[account(
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    /// CHECK: May not exist yet - `load_user_stake` checks its owner and discriminator
+    #[account(
+        seeds = [STAKE_SEED, state.key().as_ref(), user.as_ref()],
+        bump
+    )]
+    pub user_stake_info: UncheckedAccount<'info>,
+}
+
+pub fn pending_rewards_handler(ctx: Context<PendingRewards>, _user: Pubkey) -> Result<u64> {
+    let Some(user_stake) = load_user_stake(&ctx.accounts.user_stake_info)? else {
+        return Ok(0);
+    };
+
+    let clock = Clock::get()?;
+    owed_rewards(&ctx.accounts.state, &user_stake, clock.unix_timestamp)
+}
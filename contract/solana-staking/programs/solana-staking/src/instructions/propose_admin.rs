@@ -0,0 +1,37 @@
+use crate::constants::*;
+use crate::events::AdminTransferProposed;
+use crate::state::GlobalState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = admin
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn propose_admin_handler(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+
+    // Proposing the default pubkey cancels a pending transfer
+    state.pending_admin = new_admin;
+
+    let clock = Clock::get()?;
+
+    msg!("Proposed {} as new admin", new_admin);
+
+    // Emit event
+    emit!(AdminTransferProposed {
+        admin: ctx.accounts.admin.key(),
+        pending_admin: new_admin,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::ReceiptTransferred;
+use crate::state::{GlobalState, UserStakeInfo};
+use crate::utils::{
+    accrue_pending_rewards, checkpoint_reward_streams, checkpoint_user, is_blacklisted,
+    load_reward_streams, reset_empty_position, settle_reward_streams, update_reward_index,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHookAccount;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Account as TokenAccountState;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct ReceiptTransferHook<'info> {
+    #[account(token::mint = receipt_mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = state.receipt_mint)]
+    pub receipt_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = receipt_mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Source owner or delegate, already validated by the token program
+    pub authority: UncheckedAccount<'info>,
+
+    /// CHECK: Only used to resolve the accounts below
+    #[account(
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, receipt_mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    /// The receipts' position; moves out with them
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, state.key().as_ref(), source_token.owner.as_ref()],
+        bump = source_stake_info.bump
+    )]
+    pub source_stake_info: Box<Account<'info, UserStakeInfo>>,
+
+    /// Must already exist - see `open_stake_account`
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, state.key().as_ref(), destination_token.owner.as_ref()],
+        bump = destination_stake_info.bump
+    )]
+    pub destination_stake_info: Box<Account<'info, UserStakeInfo>>,
+
+    /// CHECK: This account may or may not exist - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), source_token.owner.as_ref()],
+        bump,
+    )]
+    pub source_blacklist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: This account may or may not exist - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), destination_token.owner.as_ref()],
+        bump,
+    )]
+    pub destination_blacklist_entry: UncheckedAccount<'info>,
+}
+
+/// Moves `amount` of stake from the sender's position to the receiver's as part of a
+/// receipt transfer, so a position is always backed by the receipts its owner holds.
+/// The token program cannot be re-entered from here, so rewards earned up to now are
+/// added to each side's `unpaid_rewards` instead of being paid out.
+pub fn receipt_transfer_hook_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ReceiptTransferHook<'info>>,
+    amount: u64,
+) -> Result<()> {
+    // Only Token-2022 may call in, mid-transfer; a direct call proves nothing
+    let source_info = ctx.accounts.source_token.to_account_info();
+    let source_data = source_info.try_borrow_data()?;
+    let source_account = StateWithExtensions::<TokenAccountState>::unpack(&source_data)?;
+    let transferring = source_account
+        .get_extension::<TransferHookAccount>()
+        .map(|extension| bool::from(extension.transferring))
+        .unwrap_or(false);
+    require!(transferring, StakingError::NotTransferring);
+
+    let clock = Clock::get()?;
+    let from = ctx.accounts.source_token.owner;
+    let to = ctx.accounts.destination_token.owner;
+
+    require!(
+        !is_blacklisted(&ctx.accounts.source_blacklist_entry, clock.unix_timestamp)?
+            && !is_blacklisted(
+                &ctx.accounts.destination_blacklist_entry,
+                clock.unix_timestamp
+            )?,
+        StakingError::AddressBlacklisted
+    );
+
+    // Moving receipts between one owner's own accounts leaves the position as it is
+    if from == to {
+        return Ok(());
+    }
+
+    let state = &mut ctx.accounts.state;
+    let source_stake = &mut ctx.accounts.source_stake_info;
+    let destination_stake = &mut ctx.accounts.destination_stake_info;
+
+    // A transfer unstakes on one side and stakes on the other
+    require!(
+        state.paused & (PAUSE_STAKE | PAUSE_UNSTAKE) == 0,
+        StakingError::OperationPaused
+    );
+    require!(
+        clock.unix_timestamp >= source_stake.lock_until,
+        StakingError::StakeLocked
+    );
+    require!(
+        source_stake.amount >= amount,
+        StakingError::InsufficientStakedAmount
+    );
+    // Receipts cannot carry an unlisted wallet into a permissioned pool
+    require!(
+        state.allowlist_root == [0; 32] || destination_stake.allowlist_verified,
+        StakingError::NotAllowlisted
+    );
+
+    // Settle both sides before their amounts change
+    update_reward_index(state, clock.unix_timestamp)?;
+    let mut reward_streams = load_reward_streams(state, ctx.remaining_accounts)?;
+    settle_reward_streams(
+        state,
+        &mut reward_streams,
+        source_stake,
+        clock.unix_timestamp,
+    )?;
+    settle_reward_streams(
+        state,
+        &mut reward_streams,
+        destination_stake,
+        clock.unix_timestamp,
+    )?;
+    accrue_pending_rewards(state, source_stake, clock.unix_timestamp)?;
+    accrue_pending_rewards(state, destination_stake, clock.unix_timestamp)?;
+
+    source_stake.amount -= amount;
+    destination_stake.amount = destination_stake
+        .amount
+        .checked_add(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    if destination_stake.stake_timestamp == 0 {
+        destination_stake.stake_timestamp = clock.unix_timestamp;
+    }
+
+    // total_staked is unchanged; both positions accrue from the current indices
+    checkpoint_user(state, source_stake, clock.unix_timestamp)?;
+    checkpoint_reward_streams(&reward_streams, source_stake)?;
+    checkpoint_user(state, destination_stake, clock.unix_timestamp)?;
+    checkpoint_reward_streams(&reward_streams, destination_stake)?;
+    reset_empty_position(source_stake);
+
+    msg!(
+        "Receipt transfer moved {} stake from {} to {}",
+        amount,
+        from,
+        to
+    );
+
+    // Emit event
+    emit!(ReceiptTransferred {
+        from,
+        to,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,25 +1,37 @@
 use crate::constants::*;
 use crate::errors::StakingError;
 use crate::events::RemovedFromBlacklist;
-use crate::state::{BlacklistEntry, GlobalState};
+use crate::state::{BlacklistEntry, GlobalState, Role, RoleAssignment};
+use crate::utils::require_blacklist_authority;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 #[instruction(address: Pubkey)]
 pub struct RemoveFromBlacklist<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>, // Pool admin or blacklist manager
 
     #[account(
         seeds = [STATE_SEED, state.staking_mint.as_ref()],
-        bump = state.bump,
-        has_one = admin
+        bump = state.bump
     )]
     pub state: Box<Account<'info, GlobalState>>,
 
+    /// Only required when `authority` is not the pool admin
+    #[account(
+        seeds = [
+            ROLE_SEED,
+            state.key().as_ref(),
+            &[Role::BlacklistManager as u8],
+            authority.key().as_ref()
+        ],
+        bump = blacklist_manager.bump
+    )]
+    pub blacklist_manager: Option<Box<Account<'info, RoleAssignment>>>,
+
     #[account(
         mut,
-        close = admin,
+        close = authority,
         seeds = [BLACKLIST_SEED, state.key().as_ref(), address.as_ref()],
         bump = blacklist_entry.bump,
         constraint = blacklist_entry.address == address @ StakingError::AddressNotBlacklisted
@@ -31,6 +43,12 @@ pub fn remove_from_blacklist_handler(
     ctx: Context<RemoveFromBlacklist>,
     address: Pubkey,
 ) -> Result<()> {
+    require_blacklist_authority(
+        &ctx.accounts.state,
+        &ctx.accounts.authority,
+        &ctx.accounts.blacklist_manager,
+    )?;
+
     let clock = Clock::get()?;
 
     msg!("Removed {} from blacklist", address);
@@ -38,7 +56,8 @@ pub fn remove_from_blacklist_handler(
     // Emit event
     emit!(RemovedFromBlacklist {
         address,
-        admin: ctx.accounts.admin.key(),
+        admin: ctx.accounts.state.admin,
+        authority: ctx.accounts.authority.key(),
         timestamp: clock.unix_timestamp,
     });
 
@@ -0,0 +1,81 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::RemovedFromBlacklist;
+use crate::state::{GlobalState, Role, RoleAssignment};
+use crate::utils::{close_pda_account, require_blacklist_authority};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RemoveFromBlacklistBatch<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>, // Pool admin or blacklist manager
+
+    #[account(
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    /// Only required when `authority` is not the pool admin
+    #[account(
+        seeds = [
+            ROLE_SEED,
+            state.key().as_ref(),
+            &[Role::BlacklistManager as u8],
+            authority.key().as_ref()
+        ],
+        bump = blacklist_manager.bump
+    )]
+    pub blacklist_manager: Option<Box<Account<'info, RoleAssignment>>>,
+    // remaining_accounts: one writable BlacklistEntry PDA per address, in the same order
+}
+
+pub fn remove_from_blacklist_batch_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RemoveFromBlacklistBatch<'info>>,
+    addresses: Vec<Pubkey>,
+) -> Result<()> {
+    require_blacklist_authority(
+        &ctx.accounts.state,
+        &ctx.accounts.authority,
+        &ctx.accounts.blacklist_manager,
+    )?;
+    require!(
+        addresses.len() == ctx.remaining_accounts.len(),
+        StakingError::BlacklistBatchMismatch
+    );
+
+    let state_key = ctx.accounts.state.key();
+    let authority_info = ctx.accounts.authority.to_account_info();
+    let clock = Clock::get()?;
+
+    for (address, entry_info) in addresses.iter().zip(ctx.remaining_accounts.iter()) {
+        let (expected_entry, _) = Pubkey::find_program_address(
+            &[BLACKLIST_SEED, state_key.as_ref(), address.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            entry_info.key(),
+            expected_entry,
+            StakingError::InvalidBlacklistEntry
+        );
+
+        // Skip addresses that are not listed (or were already removed earlier in the batch)
+        if entry_info.owner != &crate::ID || entry_info.data_is_empty() {
+            continue;
+        }
+
+        close_pda_account(entry_info, &authority_info)?;
+
+        msg!("Removed {} from blacklist", address);
+
+        // Emit event
+        emit!(RemovedFromBlacklist {
+            address: *address,
+            admin: ctx.accounts.state.admin,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::UnstakeRequested;
+use crate::state::{GlobalState, UserStakeInfo};
+use crate::utils::{
+    burn_receipt, checkpoint_reward_streams, checkpoint_user, claim_pending_rewards,
+    is_blacklisted, load_reward_streams, require_recipient_not_blacklisted, reset_empty_position,
+    settle_reward_streams, update_reward_index,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = reward_mint
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, state.key().as_ref(), user.key().as_ref()],
+        bump = user_stake_info.bump
+    )]
+    pub user_stake_info: Box<Account<'info, UserStakeInfo>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_SEED, state.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Must belong to the registered reward recipient, or to the user when none is set
+    #[account(
+        mut,
+        token::mint = state.reward_mint,
+        constraint = user_reward_account.owner
+            == user_stake_info.reward_recipient.unwrap_or(user.key())
+            @ StakingError::InvalidRewardRecipient
+    )]
+    pub user_reward_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: This account may or may not exist - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Only required when the pool has a receipt mint
+    #[account(mut, address = state.receipt_mint)]
+    pub receipt_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        token::mint = state.receipt_mint,
+        token::authority = user
+    )]
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub receipt_token_program: Option<Program<'info, Token2022>>,
+
+    /// CHECK: Only required when a reward recipient is set - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), user_reward_account.owner.as_ref()],
+        bump,
+    )]
+    pub recipient_blacklist_entry: Option<UncheckedAccount<'info>>,
+}
+
+pub fn request_unstake_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RequestUnstake<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, StakingError::InvalidUnstakeAmount);
+    require!(
+        ctx.accounts.state.paused & PAUSE_UNSTAKE == 0,
+        StakingError::OperationPaused
+    );
+
+    require!(
+        !is_blacklisted(
+            &ctx.accounts.blacklist_entry,
+            ctx.accounts.clock.unix_timestamp
+        )?,
+        StakingError::AddressBlacklisted
+    );
+    require_recipient_not_blacklisted(
+        &ctx.accounts.user_stake_info,
+        ctx.accounts.recipient_blacklist_entry.as_deref(),
+        ctx.accounts.clock.unix_timestamp,
+    )?;
+
+    let state = &mut ctx.accounts.state;
+    let user_stake = &mut ctx.accounts.user_stake_info;
+    let clock = &ctx.accounts.clock;
+
+    require!(
+        user_stake.amount >= amount,
+        StakingError::InsufficientStakedAmount
+    );
+    require!(
+        clock.unix_timestamp >= user_stake.lock_until,
+        StakingError::StakeLocked
+    );
+
+    // Checkpoint global and per-stream accrual before total_staked changes
+    update_reward_index(state, clock.unix_timestamp)?;
+    let mut reward_streams = load_reward_streams(state, ctx.remaining_accounts)?;
+    settle_reward_streams(state, &mut reward_streams, user_stake, clock.unix_timestamp)?;
+
+    // Pay out rewards earned so far; the unbonding amount stops earning from here
+    let rewards = claim_pending_rewards(
+        state,
+        user_stake,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.user_reward_account,
+        &ctx.accounts.token_program,
+        ctx.remaining_accounts,
+        clock,
+    )?;
+
+    // Unbonding principal no longer backs receipts
+    burn_receipt(
+        state,
+        ctx.accounts.receipt_mint.as_deref(),
+        ctx.accounts.user_receipt_account.as_deref(),
+        &ctx.accounts.user,
+        ctx.accounts.receipt_token_program.as_ref(),
+        amount,
+    )?;
+
+    // Move the amount from the active stake into the pending withdrawal
+    user_stake.amount = user_stake
+        .amount
+        .checked_sub(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    user_stake.unbonding_amount = user_stake
+        .unbonding_amount
+        .checked_add(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    // Each request restarts the cooldown for the whole pending amount
+    user_stake.unbonding_available_at = clock
+        .unix_timestamp
+        .checked_add(state.cooldown_seconds)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    checkpoint_user(state, user_stake, clock.unix_timestamp)?;
+    checkpoint_reward_streams(&reward_streams, user_stake)?;
+    reset_empty_position(user_stake);
+
+    // Update global state
+    state.total_staked = state
+        .total_staked
+        .checked_sub(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    msg!(
+        "User {} requested unstake of {} tokens, available at {}",
+        ctx.accounts.user.key(),
+        amount,
+        user_stake.unbonding_available_at
+    );
+
+    // Emit unstake requested event
+    emit!(UnstakeRequested {
+        user: ctx.accounts.user.key(),
+        amount,
+        unbonding_amount: user_stake.unbonding_amount,
+        available_at: user_stake.unbonding_available_at,
+        rewards,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::constants::*;
+use crate::events::RoleRevoked;
+use crate::state::{GlobalState, Role, RoleAssignment};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(role: Role, member: Pubkey)]
+pub struct RevokeRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = admin
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [ROLE_SEED, state.key().as_ref(), &[role as u8], member.as_ref()],
+        bump = role_assignment.bump
+    )]
+    pub role_assignment: Box<Account<'info, RoleAssignment>>,
+}
+
+pub fn revoke_role_handler(ctx: Context<RevokeRole>, role: Role, member: Pubkey) -> Result<()> {
+    let clock = Clock::get()?;
+
+    msg!("Revoked {:?} from {}", role, member);
+
+    // Emit event
+    emit!(RoleRevoked {
+        admin: ctx.accounts.admin.key(),
+        role,
+        member,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
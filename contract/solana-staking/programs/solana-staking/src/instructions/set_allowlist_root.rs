@@ -0,0 +1,39 @@
+use crate::constants::*;
+use crate::events::AllowlistRootUpdated;
+use crate::state::GlobalState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetAllowlistRoot<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = admin
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn set_allowlist_root_handler(
+    ctx: Context<SetAllowlistRoot>,
+    allowlist_root: [u8; 32],
+) -> Result<()> {
+    // An all-zero root opens the pool; wallets verified under an older root stay verified
+    let state = &mut ctx.accounts.state;
+    state.allowlist_root = allowlist_root;
+
+    let clock = Clock::get()?;
+
+    msg!("Allowlist root updated");
+
+    // Emit event
+    emit!(AllowlistRootUpdated {
+        admin: ctx.accounts.admin.key(),
+        allowlist_root,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
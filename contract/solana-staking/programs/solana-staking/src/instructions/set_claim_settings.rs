@@ -0,0 +1,58 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::ClaimSettingsUpdated;
+use crate::state::{GlobalState, UserStakeInfo};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetClaimSettings<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, state.key().as_ref(), user.key().as_ref()],
+        bump = user_stake_info.bump
+    )]
+    pub user_stake_info: Box<Account<'info, UserStakeInfo>>,
+}
+
+pub fn set_claim_settings_handler(
+    ctx: Context<SetClaimSettings>,
+    reward_recipient: Option<Pubkey>,
+    claim_delegate: Option<Pubkey>,
+) -> Result<()> {
+    require!(
+        reward_recipient != Some(Pubkey::default()) && claim_delegate != Some(Pubkey::default()),
+        StakingError::InvalidClaimSettings
+    );
+
+    // None restores the defaults: only the owner claims, into their own account
+    let user_stake = &mut ctx.accounts.user_stake_info;
+    user_stake.reward_recipient = reward_recipient;
+    user_stake.claim_delegate = claim_delegate;
+
+    let clock = Clock::get()?;
+
+    msg!(
+        "User {} set reward recipient {:?} and claim delegate {:?}",
+        ctx.accounts.user.key(),
+        reward_recipient,
+        claim_delegate
+    );
+
+    // Emit event
+    emit!(ClaimSettingsUpdated {
+        user: ctx.accounts.user.key(),
+        reward_recipient,
+        claim_delegate,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
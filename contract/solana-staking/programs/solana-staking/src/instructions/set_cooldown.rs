@@ -0,0 +1,39 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::CooldownUpdated;
+use crate::state::GlobalState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetCooldown<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = admin
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn set_cooldown_handler(ctx: Context<SetCooldown>, cooldown_seconds: i64) -> Result<()> {
+    require!(cooldown_seconds >= 0, StakingError::InvalidCooldown);
+
+    // Pending withdrawals keep the availability time they were requested with
+    let state = &mut ctx.accounts.state;
+    state.cooldown_seconds = cooldown_seconds;
+
+    let clock = Clock::get()?;
+
+    msg!("Unstake cooldown set to {} seconds", cooldown_seconds);
+
+    // Emit event
+    emit!(CooldownUpdated {
+        admin: ctx.accounts.admin.key(),
+        cooldown_seconds,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
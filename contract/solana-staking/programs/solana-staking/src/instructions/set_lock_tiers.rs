@@ -0,0 +1,62 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::LockTiersUpdated;
+use crate::state::{GlobalState, LockTier, RewardMode};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetLockTiers<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = admin
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn set_lock_tiers_handler(ctx: Context<SetLockTiers>, tiers: Vec<LockTier>) -> Result<()> {
+    // Boosts are paid on top of a per-staker rate; a shared emission budget
+    // would need boosted weights in total_staked instead
+    require!(
+        ctx.accounts.state.reward_mode == RewardMode::FixedRate,
+        StakingError::InvalidRewardMode
+    );
+    require!(tiers.len() <= MAX_LOCK_TIERS, StakingError::InvalidLockTier);
+
+    // Longer locks must not earn a smaller boost
+    let mut previous = LockTier {
+        duration: 0,
+        multiplier_bps: BPS_DENOMINATOR,
+    };
+    for tier in &tiers {
+        require!(
+            tier.duration > previous.duration
+                && tier.multiplier_bps >= previous.multiplier_bps
+                && tier.multiplier_bps <= MAX_LOCK_MULTIPLIER_BPS,
+            StakingError::InvalidLockTier
+        );
+        previous = *tier;
+    }
+
+    // Existing locks keep the multiplier they were taken with
+    let state = &mut ctx.accounts.state;
+    state.lock_tiers = [LockTier::default(); MAX_LOCK_TIERS];
+    state.lock_tiers[..tiers.len()].copy_from_slice(&tiers);
+    state.lock_tier_count = tiers.len() as u8;
+
+    let clock = Clock::get()?;
+
+    msg!("Configured {} lock tiers", tiers.len());
+
+    // Emit event
+    emit!(LockTiersUpdated {
+        admin: ctx.accounts.admin.key(),
+        tiers,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use crate::constants::*;
+use crate::events::QuarantineUpdated;
+use crate::state::GlobalState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetQuarantine<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = admin
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn set_quarantine_handler(ctx: Context<SetQuarantine>, quarantine: Pubkey) -> Result<()> {
+    // Setting the default pubkey disables force_unstake again
+    let state = &mut ctx.accounts.state;
+    state.quarantine = quarantine;
+
+    let clock = Clock::get()?;
+
+    msg!("Quarantine owner set to {}", quarantine);
+
+    // Emit event
+    emit!(QuarantineUpdated {
+        admin: ctx.accounts.admin.key(),
+        quarantine,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::RewardCampaignUpdated;
+use crate::state::GlobalState;
+use crate::utils::{
+    begin_reward_epoch, load_reward_streams, update_reward_index, update_reward_stream,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRewardCampaign<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = admin
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn set_reward_campaign_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SetRewardCampaign<'info>>,
+    rewards_start: i64,
+    rewards_end: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        rewards_start < rewards_end && rewards_end > clock.unix_timestamp,
+        StakingError::InvalidRewardWindow
+    );
+
+    let state = &mut ctx.accounts.state;
+
+    // Settle accrual under the current window; the new one only applies from now on
+    update_reward_index(state, clock.unix_timestamp)?;
+    let mut reward_streams = load_reward_streams(state, ctx.remaining_accounts)?;
+    for stream in reward_streams.iter_mut() {
+        update_reward_stream(state, stream, clock.unix_timestamp)?;
+        stream.exit(&crate::ID)?;
+    }
+    begin_reward_epoch(state, clock.unix_timestamp)?;
+
+    state.rewards_start = rewards_start;
+    state.rewards_end = rewards_end;
+
+    msg!(
+        "Reward campaign set to [{}, {}]",
+        rewards_start,
+        rewards_end
+    );
+
+    // Emit event
+    emit!(RewardCampaignUpdated {
+        admin: ctx.accounts.admin.key(),
+        rewards_start,
+        rewards_end,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -2,9 +2,12 @@ use crate::constants::*;
 use crate::errors::StakingError;
 use crate::events::Staked;
 use crate::state::{GlobalState, UserStakeInfo};
-use crate::utils::claim_pending_rewards;
+use crate::utils::{
+    deposit_stake, is_blacklisted, require_recipient_not_blacklisted, DepositAccounts,
+};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct Stake<'info> {
@@ -14,10 +17,16 @@ pub struct Stake<'info> {
     #[account(
         mut,
         seeds = [STATE_SEED, state.staking_mint.as_ref()],
-        bump = state.bump
+        bump = state.bump,
+        has_one = staking_mint,
+        has_one = reward_mint
     )]
     pub state: Box<Account<'info, GlobalState>>,
 
+    pub staking_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
     #[account(
         init_if_needed,
         payer = user,
@@ -32,100 +41,127 @@ pub struct Stake<'info> {
         token::mint = state.staking_mint,
         token::authority = user
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         seeds = [STAKING_VAULT_SEED, state.key().as_ref()],
         bump
     )]
-    pub staking_vault: Account<'info, TokenAccount>,
+    pub staking_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         seeds = [REWARD_VAULT_SEED, state.key().as_ref()],
         bump
     )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
 
+    /// Must belong to the registered reward recipient, or to the user when none is set
     #[account(
         mut,
         token::mint = state.reward_mint,
-        token::authority = user
+        constraint = user_reward_account.owner
+            == user_stake_info.reward_recipient.unwrap_or(user.key())
+            @ StakingError::InvalidRewardRecipient
     )]
-    pub user_reward_account: Account<'info, TokenAccount>,
+    pub user_reward_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// CHECK: This account may or may not exist - we check if it exists to determine blacklist status
+    /// CHECK: This account may or may not exist - `is_blacklisted` checks its owner and discriminator
     #[account(
         seeds = [BLACKLIST_SEED, state.key().as_ref(), user.key().as_ref()],
         bump,
     )]
     pub blacklist_entry: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub clock: Sysvar<'info, Clock>,
+
+    /// Only required when the pool has a receipt mint
+    #[account(mut, address = state.receipt_mint)]
+    pub receipt_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        token::mint = state.receipt_mint,
+        token::authority = user
+    )]
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub receipt_token_program: Option<Program<'info, Token2022>>,
+
+    /// CHECK: Only required when a reward recipient is set - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), user_reward_account.owner.as_ref()],
+        bump,
+    )]
+    pub recipient_blacklist_entry: Option<UncheckedAccount<'info>>,
 }
 
-pub fn stake_handler(ctx: Context<Stake>, amount: u64) -> Result<()> {
+pub fn stake_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Stake<'info>>,
+    amount: u64,
+    lock_tier: Option<u8>,
+    allowlist_proof: Vec<[u8; 32]>,
+) -> Result<()> {
     require!(amount > 0, StakingError::InvalidStakeAmount);
+    require!(
+        ctx.accounts.state.paused & PAUSE_STAKE == 0,
+        StakingError::OperationPaused
+    );
 
-    let blacklist_info = &ctx.accounts.blacklist_entry.to_account_info();
     require!(
-        blacklist_info.data_is_empty() || blacklist_info.lamports() == 0,
+        !is_blacklisted(
+            &ctx.accounts.blacklist_entry,
+            ctx.accounts.clock.unix_timestamp
+        )?,
         StakingError::AddressBlacklisted
     );
+    require_recipient_not_blacklisted(
+        &ctx.accounts.user_stake_info,
+        ctx.accounts.recipient_blacklist_entry.as_deref(),
+        ctx.accounts.clock.unix_timestamp,
+    )?;
+
+    ctx.accounts.user_stake_info.bump = ctx.bumps.user_stake_info;
+
+    let accounts = &mut *ctx.accounts;
+    let amount = deposit_stake(
+        &mut accounts.state,
+        &mut accounts.user_stake_info,
+        accounts.user.key(),
+        DepositAccounts {
+            depositor: &accounts.user,
+            depositor_token_account: &accounts.user_token_account,
+            staking_mint: &accounts.staking_mint,
+            staking_vault: &mut accounts.staking_vault,
+            reward_vault: &accounts.reward_vault,
+            reward_mint: &accounts.reward_mint,
+            reward_account: &accounts.user_reward_account,
+            token_program: &accounts.token_program,
+            receipt_mint: accounts.receipt_mint.as_deref(),
+            receipt_account: accounts.user_receipt_account.as_deref(),
+            receipt_token_program: accounts.receipt_token_program.as_ref(),
+            clock: &accounts.clock,
+        },
+        ctx.remaining_accounts,
+        amount,
+        lock_tier,
+        &allowlist_proof,
+    )?;
 
-    let state = &mut ctx.accounts.state;
-    let user_stake = &mut ctx.accounts.user_stake_info;
+    let user_stake = &ctx.accounts.user_stake_info;
     let clock = &ctx.accounts.clock;
 
-    // If user already has a stake, claim rewards first
-    if user_stake.amount > 0 {
-        claim_pending_rewards(
-            state,
-            user_stake,
-            &ctx.accounts.reward_vault,
-            &ctx.accounts.user_reward_account,
-            &ctx.accounts.token_program,
-            clock,
-        )?;
-    }
-
-    // Transfer staking tokens from user to vault
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.user_token_account.to_account_info(),
-        to: ctx.accounts.staking_vault.to_account_info(),
-        authority: ctx.accounts.user.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    token::transfer(cpi_ctx, amount)?;
-
-    // Update user stake info
-    user_stake.owner = ctx.accounts.user.key();
-    user_stake.amount = user_stake
-        .amount
-        .checked_add(amount)
-        .ok_or(StakingError::ArithmeticOverflow)?;
-    // Only update stake_timestamp on first stake
-    if user_stake.stake_timestamp == 0 {
-        user_stake.stake_timestamp = clock.unix_timestamp;
-    }
-    user_stake.bump = ctx.bumps.user_stake_info;
-
-    // Update global state
-    state.total_staked = state
-        .total_staked
-        .checked_add(amount)
-        .ok_or(StakingError::ArithmeticOverflow)?;
-
     msg!("User {} staked {} tokens", ctx.accounts.user.key(), amount);
 
     // Emit staked event
     emit!(Staked {
         user: ctx.accounts.user.key(),
         amount,
+        lock_until: user_stake.lock_until,
+        multiplier_bps: user_stake.multiplier_bps,
         timestamp: clock.unix_timestamp,
     });
 
@@ -0,0 +1,197 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::{Staked, StakedFor};
+use crate::state::{GlobalState, UserStakeInfo};
+use crate::utils::{
+    deposit_stake, is_blacklisted, require_recipient_not_blacklisted, DepositAccounts,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey)]
+pub struct StakeFor<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = staking_mint,
+        has_one = reward_mint
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    pub staking_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + UserStakeInfo::INIT_SPACE,
+        seeds = [STAKE_SEED, state.key().as_ref(), beneficiary.as_ref()],
+        bump
+    )]
+    pub user_stake_info: Box<Account<'info, UserStakeInfo>>,
+
+    #[account(
+        mut,
+        token::mint = state.staking_mint,
+        token::authority = payer
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_VAULT_SEED, state.key().as_ref()],
+        bump
+    )]
+    pub staking_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_SEED, state.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the beneficiary's pending rewards when topping up an existing position.
+    /// Must belong to their reward recipient, or to the beneficiary when none is set
+    #[account(
+        mut,
+        token::mint = state.reward_mint,
+        constraint = beneficiary_reward_account.owner
+            == user_stake_info.reward_recipient.unwrap_or(beneficiary)
+            @ StakingError::InvalidRewardRecipient
+    )]
+    pub beneficiary_reward_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: This account may or may not exist - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), payer.key().as_ref()],
+        bump,
+    )]
+    pub payer_blacklist_entry: UncheckedAccount<'info>,
+
+    /// CHECK: This account may or may not exist - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), beneficiary.as_ref()],
+        bump,
+    )]
+    pub beneficiary_blacklist_entry: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Only required when the pool has a receipt mint
+    #[account(mut, address = state.receipt_mint)]
+    pub receipt_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        token::mint = state.receipt_mint,
+        token::authority = beneficiary
+    )]
+    pub beneficiary_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub receipt_token_program: Option<Program<'info, Token2022>>,
+
+    /// CHECK: Only required when a reward recipient is set - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), beneficiary_reward_account.owner.as_ref()],
+        bump,
+    )]
+    pub recipient_blacklist_entry: Option<UncheckedAccount<'info>>,
+}
+
+pub fn stake_for_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, StakeFor<'info>>,
+    beneficiary: Pubkey,
+    amount: u64,
+    allowlist_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    require!(amount > 0, StakingError::InvalidStakeAmount);
+    require!(
+        beneficiary != Pubkey::default(),
+        StakingError::InvalidBeneficiary
+    );
+    require!(
+        ctx.accounts.state.paused & PAUSE_STAKE == 0,
+        StakingError::OperationPaused
+    );
+
+    // Neither side of the deposit may be a blacklisted address
+    let now = ctx.accounts.clock.unix_timestamp;
+    require!(
+        !is_blacklisted(&ctx.accounts.payer_blacklist_entry, now)?,
+        StakingError::AddressBlacklisted
+    );
+    require!(
+        !is_blacklisted(&ctx.accounts.beneficiary_blacklist_entry, now)?,
+        StakingError::AddressBlacklisted
+    );
+    require_recipient_not_blacklisted(
+        &ctx.accounts.user_stake_info,
+        ctx.accounts.recipient_blacklist_entry.as_deref(),
+        now,
+    )?;
+
+    ctx.accounts.user_stake_info.bump = ctx.bumps.user_stake_info;
+
+    // Existing rewards belong to the beneficiary and are paid out before topping up
+    let accounts = &mut *ctx.accounts;
+    let amount = deposit_stake(
+        &mut accounts.state,
+        &mut accounts.user_stake_info,
+        beneficiary,
+        DepositAccounts {
+            depositor: &accounts.payer,
+            depositor_token_account: &accounts.payer_token_account,
+            staking_mint: &accounts.staking_mint,
+            staking_vault: &mut accounts.staking_vault,
+            reward_vault: &accounts.reward_vault,
+            reward_mint: &accounts.reward_mint,
+            reward_account: &accounts.beneficiary_reward_account,
+            token_program: &accounts.token_program,
+            receipt_mint: accounts.receipt_mint.as_deref(),
+            receipt_account: accounts.beneficiary_receipt_account.as_deref(),
+            receipt_token_program: accounts.receipt_token_program.as_ref(),
+            clock: &accounts.clock,
+        },
+        ctx.remaining_accounts,
+        amount,
+        None,
+        &allowlist_proof,
+    )?;
+
+    let user_stake = &ctx.accounts.user_stake_info;
+    let clock = &ctx.accounts.clock;
+
+    msg!(
+        "Payer {} staked {} tokens for {}",
+        ctx.accounts.payer.key(),
+        amount,
+        beneficiary
+    );
+
+    // Emit events; Staked keeps position indexers in sync with the beneficiary
+    emit!(Staked {
+        user: beneficiary,
+        amount,
+        lock_until: user_stake.lock_until,
+        multiplier_bps: user_stake.multiplier_bps,
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(StakedFor {
+        payer: ctx.accounts.payer.key(),
+        beneficiary,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
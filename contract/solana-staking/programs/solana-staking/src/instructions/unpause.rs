@@ -0,0 +1,46 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::Unpaused;
+use crate::state::GlobalState;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = admin
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn unpause_handler(ctx: Context<Unpause>, flags: u8) -> Result<()> {
+    require!(
+        flags != 0 && flags & !PAUSE_ALL == 0,
+        StakingError::InvalidPauseFlags
+    );
+
+    let state = &mut ctx.accounts.state;
+    state.paused &= !flags;
+
+    let clock = Clock::get()?;
+
+    msg!(
+        "Unpaused flags {:#05b}, current pause state {:#05b}",
+        flags,
+        state.paused
+    );
+
+    // Emit event
+    emit!(Unpaused {
+        admin: ctx.accounts.admin.key(),
+        flags,
+        paused: state.paused,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -2,9 +2,14 @@ use crate::constants::*;
 use crate::errors::StakingError;
 use crate::events::Unstaked;
 use crate::state::{GlobalState, UserStakeInfo};
-use crate::utils::claim_pending_rewards;
+use crate::utils::{
+    burn_receipt, checkpoint_reward_streams, checkpoint_user, claim_pending_rewards,
+    is_blacklisted, load_reward_streams, require_recipient_not_blacklisted, reset_empty_position,
+    settle_reward_streams, transfer_checked_with_hook, update_reward_index,
+};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct Unstake<'info> {
@@ -14,10 +19,16 @@ pub struct Unstake<'info> {
     #[account(
         mut,
         seeds = [STATE_SEED, state.staking_mint.as_ref()],
-        bump = state.bump
+        bump = state.bump,
+        has_one = staking_mint,
+        has_one = reward_mint
     )]
     pub state: Box<Account<'info, GlobalState>>,
 
+    pub staking_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
     #[account(
         mut,
         seeds = [STAKE_SEED, state.key().as_ref(), user.key().as_ref()],
@@ -30,48 +41,89 @@ pub struct Unstake<'info> {
         token::mint = state.staking_mint,
         token::authority = user
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         seeds = [STAKING_VAULT_SEED, state.key().as_ref()],
         bump
     )]
-    pub staking_vault: Account<'info, TokenAccount>,
+    pub staking_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
         seeds = [REWARD_VAULT_SEED, state.key().as_ref()],
         bump
     )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
 
+    /// Must belong to the registered reward recipient, or to the user when none is set
     #[account(
         mut,
         token::mint = state.reward_mint,
-        token::authority = user
+        constraint = user_reward_account.owner
+            == user_stake_info.reward_recipient.unwrap_or(user.key())
+            @ StakingError::InvalidRewardRecipient
     )]
-    pub user_reward_account: Account<'info, TokenAccount>,
+    pub user_reward_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// CHECK: This account may or may not exist - we check if it exists to determine blacklist status
+    /// CHECK: This account may or may not exist - `is_blacklisted` checks its owner and discriminator
     #[account(
         seeds = [BLACKLIST_SEED, state.key().as_ref(), user.key().as_ref()],
         bump,
     )]
     pub blacklist_entry: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub clock: Sysvar<'info, Clock>,
+
+    /// Only required when the pool has a receipt mint
+    #[account(mut, address = state.receipt_mint)]
+    pub receipt_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        token::mint = state.receipt_mint,
+        token::authority = user
+    )]
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub receipt_token_program: Option<Program<'info, Token2022>>,
+
+    /// CHECK: Only required when a reward recipient is set - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), user_reward_account.owner.as_ref()],
+        bump,
+    )]
+    pub recipient_blacklist_entry: Option<UncheckedAccount<'info>>,
 }
 
-pub fn unstake_handler(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+pub fn unstake_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Unstake<'info>>,
+    amount: u64,
+) -> Result<()> {
     require!(amount > 0, StakingError::InvalidUnstakeAmount);
+    require!(
+        ctx.accounts.state.paused & PAUSE_UNSTAKE == 0,
+        StakingError::OperationPaused
+    );
+    require!(
+        ctx.accounts.state.cooldown_seconds == 0,
+        StakingError::CooldownRequired
+    );
 
-    let blacklist_info = &ctx.accounts.blacklist_entry.to_account_info();
     require!(
-        blacklist_info.data_is_empty() || blacklist_info.lamports() == 0,
+        !is_blacklisted(
+            &ctx.accounts.blacklist_entry,
+            ctx.accounts.clock.unix_timestamp
+        )?,
         StakingError::AddressBlacklisted
     );
+    require_recipient_not_blacklisted(
+        &ctx.accounts.user_stake_info,
+        ctx.accounts.recipient_blacklist_entry.as_deref(),
+        ctx.accounts.clock.unix_timestamp,
+    )?;
 
     let state = &mut ctx.accounts.state;
     let user_stake = &mut ctx.accounts.user_stake_info;
@@ -81,39 +133,61 @@ pub fn unstake_handler(ctx: Context<Unstake>, amount: u64) -> Result<()> {
         user_stake.amount >= amount,
         StakingError::InsufficientStakedAmount
     );
+    require!(
+        clock.unix_timestamp >= user_stake.lock_until,
+        StakingError::StakeLocked
+    );
+
+    // Checkpoint global and per-stream accrual before total_staked changes
+    update_reward_index(state, clock.unix_timestamp)?;
+    let mut reward_streams = load_reward_streams(state, ctx.remaining_accounts)?;
+    settle_reward_streams(state, &mut reward_streams, user_stake, clock.unix_timestamp)?;
 
     // Calculate and transfer rewards before unstaking
     let rewards = claim_pending_rewards(
         state,
         user_stake,
         &ctx.accounts.reward_vault,
+        &ctx.accounts.reward_mint,
         &ctx.accounts.user_reward_account,
         &ctx.accounts.token_program,
+        ctx.remaining_accounts,
         clock,
     )?;
 
+    // Receipts for the unstaked principal must be returned
+    burn_receipt(
+        state,
+        ctx.accounts.receipt_mint.as_deref(),
+        ctx.accounts.user_receipt_account.as_deref(),
+        &ctx.accounts.user,
+        ctx.accounts.receipt_token_program.as_ref(),
+        amount,
+    )?;
+
     // Transfer staking tokens back to user
-    let seeds = &[
-        STATE_SEED.as_ref(),
-        state.staking_mint.as_ref(),
-        &[state.bump],
-    ];
+    let seeds = &[STATE_SEED, state.staking_mint.as_ref(), &[state.bump]];
     let signer = &[&seeds[..]];
 
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.staking_vault.to_account_info(),
-        to: ctx.accounts.user_token_account.to_account_info(),
-        authority: state.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-    token::transfer(cpi_ctx, amount)?;
+    transfer_checked_with_hook(
+        &ctx.accounts.token_program,
+        ctx.accounts.staking_vault.to_account_info(),
+        &ctx.accounts.staking_mint,
+        ctx.accounts.user_token_account.to_account_info(),
+        state.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        signer,
+    )?;
 
     // Update user stake info
     user_stake.amount = user_stake
         .amount
         .checked_sub(amount)
         .ok_or(StakingError::ArithmeticOverflow)?;
+    checkpoint_user(state, user_stake, clock.unix_timestamp)?;
+    checkpoint_reward_streams(&reward_streams, user_stake)?;
+    reset_empty_position(user_stake);
 
     // Update global state
     state.total_staked = state
@@ -0,0 +1,53 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::RewardRateUpdated;
+use crate::state::{GlobalState, RewardMode};
+use crate::utils::{begin_reward_epoch, update_reward_index};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateRewardRate<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = admin
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+}
+
+pub fn update_reward_rate_handler(ctx: Context<UpdateRewardRate>, new_rate: u64) -> Result<()> {
+    require!(
+        new_rate > 0 && new_rate <= 1000,
+        StakingError::InvalidRewardRate
+    );
+
+    let state = &mut ctx.accounts.state;
+    require!(
+        state.reward_mode == RewardMode::FixedRate,
+        StakingError::InvalidRewardMode
+    );
+
+    let clock = Clock::get()?;
+
+    // Settle the elapsed period at the old rate before switching
+    update_reward_index(state, clock.unix_timestamp)?;
+    begin_reward_epoch(state, clock.unix_timestamp)?;
+
+    let old_rate = state.reward_rate;
+    state.reward_rate = new_rate;
+
+    msg!("Reward rate updated from {} to {}", old_rate, new_rate);
+
+    // Emit event
+    emit!(RewardRateUpdated {
+        admin: ctx.accounts.admin.key(),
+        old_rate,
+        new_rate,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::Withdrawn;
+use crate::state::{GlobalState, UserStakeInfo};
+use crate::utils::{is_blacklisted, transfer_checked_with_hook};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = staking_mint
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    pub staking_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, state.key().as_ref(), user.key().as_ref()],
+        bump = user_stake_info.bump
+    )]
+    pub user_stake_info: Box<Account<'info, UserStakeInfo>>,
+
+    #[account(
+        mut,
+        token::mint = state.staking_mint,
+        token::authority = user
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKING_VAULT_SEED, state.key().as_ref()],
+        bump
+    )]
+    pub staking_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: This account may or may not exist - `is_blacklisted` checks its owner and discriminator
+    #[account(
+        seeds = [BLACKLIST_SEED, state.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub blacklist_entry: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn withdraw_handler<'info>(ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>) -> Result<()> {
+    require!(
+        ctx.accounts.state.paused & PAUSE_UNSTAKE == 0,
+        StakingError::OperationPaused
+    );
+
+    require!(
+        !is_blacklisted(
+            &ctx.accounts.blacklist_entry,
+            ctx.accounts.clock.unix_timestamp
+        )?,
+        StakingError::AddressBlacklisted
+    );
+
+    let state = &ctx.accounts.state;
+    let user_stake = &mut ctx.accounts.user_stake_info;
+    let clock = &ctx.accounts.clock;
+
+    let amount = user_stake.unbonding_amount;
+    require!(amount > 0, StakingError::NoPendingWithdrawal);
+    require!(
+        clock.unix_timestamp >= user_stake.unbonding_available_at,
+        StakingError::CooldownNotElapsed
+    );
+
+    // Transfer staking tokens back to user
+    let seeds = &[STATE_SEED, state.staking_mint.as_ref(), &[state.bump]];
+    let signer = &[&seeds[..]];
+
+    transfer_checked_with_hook(
+        &ctx.accounts.token_program,
+        ctx.accounts.staking_vault.to_account_info(),
+        &ctx.accounts.staking_mint,
+        ctx.accounts.user_token_account.to_account_info(),
+        state.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        signer,
+    )?;
+
+    user_stake.unbonding_amount = 0;
+    user_stake.unbonding_available_at = 0;
+
+    msg!(
+        "User {} withdrew {} unbonded tokens",
+        ctx.accounts.user.key(),
+        amount
+    );
+
+    // Emit withdrawn event
+    emit!(Withdrawn {
+        user: ctx.accounts.user.key(),
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
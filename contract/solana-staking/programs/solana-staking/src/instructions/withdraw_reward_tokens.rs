@@ -0,0 +1,89 @@
+use crate::constants::*;
+use crate::errors::StakingError;
+use crate::events::RewardTokensWithdrawn;
+use crate::state::GlobalState;
+use crate::utils::transfer_checked_with_hook;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct WithdrawRewardTokens<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED, state.staking_mint.as_ref()],
+        bump = state.bump,
+        has_one = admin,
+        has_one = reward_mint
+    )]
+    pub state: Box<Account<'info, GlobalState>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_SEED, state.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = state.reward_mint,
+        constraint = destination.key() != state.staking_vault @ StakingError::InvalidWithdrawDestination
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn withdraw_reward_tokens_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawRewardTokens<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, StakingError::InvalidWithdrawAmount);
+
+    let state = &ctx.accounts.state;
+
+    // Only the reward vault can be drained here; the state PDA signs as its authority
+    let seeds = &[STATE_SEED, state.staking_mint.as_ref(), &[state.bump]];
+    let signer = &[&seeds[..]];
+
+    transfer_checked_with_hook(
+        &ctx.accounts.token_program,
+        ctx.accounts.reward_vault.to_account_info(),
+        &ctx.accounts.reward_mint,
+        ctx.accounts.destination.to_account_info(),
+        state.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        signer,
+    )?;
+
+    // Keep the funding totals reconcilable against the vault balance
+    let state = &mut ctx.accounts.state;
+    state.total_withdrawn = state
+        .total_withdrawn
+        .checked_add(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    let clock = Clock::get()?;
+
+    msg!(
+        "Admin withdrew {} reward tokens to {}",
+        amount,
+        ctx.accounts.destination.key()
+    );
+
+    // Emit event
+    emit!(RewardTokensWithdrawn {
+        admin: ctx.accounts.admin.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+        total_withdrawn: state.total_withdrawn,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use spl_discriminator::SplDiscriminate;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
 
 pub mod constants;
 pub mod errors;
@@ -8,6 +10,7 @@ pub mod state;
 pub mod utils;
 
 use instructions::*;
+use state::{LockTier, RewardMode, Role};
 
 declare_id!("1gGFthN24CB1p2LEvmhpnJVHAHm3koZDQnHgDoe6Ra2");
 
@@ -15,27 +18,252 @@ declare_id!("1gGFthN24CB1p2LEvmhpnJVHAHm3koZDQnHgDoe6Ra2");
 pub mod solana_staking {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>, reward_rate: u64) -> Result<()> {
-        instructions::initialize::initialize_handler(ctx, reward_rate)
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        reward_rate: u64,
+        reward_mode: RewardMode,
+        reward_per_second: u64,
+    ) -> Result<()> {
+        instructions::initialize::initialize_handler(
+            ctx,
+            reward_rate,
+            reward_mode,
+            reward_per_second,
+        )
     }
 
-    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
-        instructions::stake::stake_handler(ctx, amount)
+    pub fn stake<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Stake<'info>>,
+        amount: u64,
+        lock_tier: Option<u8>,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::stake::stake_handler(ctx, amount, lock_tier, allowlist_proof)
     }
 
-    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+    pub fn unstake<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Unstake<'info>>,
+        amount: u64,
+    ) -> Result<()> {
         instructions::unstake::unstake_handler(ctx, amount)
     }
 
-    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    pub fn claim_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimRewards<'info>>,
+    ) -> Result<()> {
         instructions::claim_rewards::claim_rewards_handler(ctx)
     }
 
-    pub fn add_to_blacklist(ctx: Context<AddToBlacklist>, address: Pubkey) -> Result<()> {
-        instructions::add_to_blacklist::add_to_blacklist_handler(ctx, address)
+    pub fn add_to_blacklist(
+        ctx: Context<AddToBlacklist>,
+        address: Pubkey,
+        reason_code: u16,
+        reference: Option<[u8; 32]>,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        instructions::add_to_blacklist::add_to_blacklist_handler(
+            ctx,
+            address,
+            reason_code,
+            reference,
+            expires_at,
+        )
     }
 
     pub fn remove_from_blacklist(ctx: Context<RemoveFromBlacklist>, address: Pubkey) -> Result<()> {
         instructions::remove_from_blacklist::remove_from_blacklist_handler(ctx, address)
     }
+
+    pub fn add_to_blacklist_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddToBlacklistBatch<'info>>,
+        addresses: Vec<Pubkey>,
+        reason_code: u16,
+        reference: Option<[u8; 32]>,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        instructions::add_to_blacklist_batch::add_to_blacklist_batch_handler(
+            ctx,
+            addresses,
+            reason_code,
+            reference,
+            expires_at,
+        )
+    }
+
+    pub fn remove_from_blacklist_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemoveFromBlacklistBatch<'info>>,
+        addresses: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::remove_from_blacklist_batch::remove_from_blacklist_batch_handler(
+            ctx, addresses,
+        )
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, member: Pubkey) -> Result<()> {
+        instructions::grant_role::grant_role_handler(ctx, role, member)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: Role, member: Pubkey) -> Result<()> {
+        instructions::revoke_role::revoke_role_handler(ctx, role, member)
+    }
+
+    pub fn update_reward_rate(ctx: Context<UpdateRewardRate>, new_rate: u64) -> Result<()> {
+        instructions::update_reward_rate::update_reward_rate_handler(ctx, new_rate)
+    }
+
+    pub fn withdraw_reward_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawRewardTokens<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_reward_tokens::withdraw_reward_tokens_handler(ctx, amount)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::propose_admin::propose_admin_handler(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::accept_admin_handler(ctx)
+    }
+
+    pub fn pause(ctx: Context<Pause>, flags: u8) -> Result<()> {
+        instructions::pause::pause_handler(ctx, flags)
+    }
+
+    pub fn unpause(ctx: Context<Unpause>, flags: u8) -> Result<()> {
+        instructions::unpause::unpause_handler(ctx, flags)
+    }
+
+    pub fn add_reward_stream(ctx: Context<AddRewardStream>, reward_per_second: u64) -> Result<()> {
+        instructions::add_reward_stream::add_reward_stream_handler(ctx, reward_per_second)
+    }
+
+    pub fn claim_stream_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimStreamRewards<'info>>,
+    ) -> Result<()> {
+        instructions::claim_stream_rewards::claim_stream_rewards_handler(ctx)
+    }
+
+    pub fn set_lock_tiers(ctx: Context<SetLockTiers>, tiers: Vec<LockTier>) -> Result<()> {
+        instructions::set_lock_tiers::set_lock_tiers_handler(ctx, tiers)
+    }
+
+    pub fn set_reward_campaign<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetRewardCampaign<'info>>,
+        rewards_start: i64,
+        rewards_end: i64,
+    ) -> Result<()> {
+        instructions::set_reward_campaign::set_reward_campaign_handler(
+            ctx,
+            rewards_start,
+            rewards_end,
+        )
+    }
+
+    pub fn set_cooldown(ctx: Context<SetCooldown>, cooldown_seconds: i64) -> Result<()> {
+        instructions::set_cooldown::set_cooldown_handler(ctx, cooldown_seconds)
+    }
+
+    pub fn request_unstake<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RequestUnstake<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::request_unstake::request_unstake_handler(ctx, amount)
+    }
+
+    pub fn withdraw<'info>(ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>) -> Result<()> {
+        instructions::withdraw::withdraw_handler(ctx)
+    }
+
+    pub fn cancel_unstake<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelUnstake<'info>>,
+    ) -> Result<()> {
+        instructions::cancel_unstake::cancel_unstake_handler(ctx)
+    }
+
+    pub fn enable_receipt_mint(ctx: Context<EnableReceiptMint>) -> Result<()> {
+        instructions::enable_receipt_mint::enable_receipt_mint_handler(ctx)
+    }
+
+    pub fn open_stake_account(ctx: Context<OpenStakeAccount>) -> Result<()> {
+        instructions::open_stake_account::open_stake_account_handler(ctx)
+    }
+
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn receipt_transfer_hook<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReceiptTransferHook<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::receipt_transfer_hook::receipt_transfer_hook_handler(ctx, amount)
+    }
+
+    pub fn compound<'info>(ctx: Context<'_, '_, 'info, 'info, Compound<'info>>) -> Result<()> {
+        instructions::compound::compound_handler(ctx)
+    }
+
+    pub fn set_quarantine(ctx: Context<SetQuarantine>, quarantine: Pubkey) -> Result<()> {
+        instructions::set_quarantine::set_quarantine_handler(ctx, quarantine)
+    }
+
+    pub fn force_unstake<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ForceUnstake<'info>>,
+        user: Pubkey,
+        cancel_rewards: bool,
+    ) -> Result<()> {
+        instructions::force_unstake::force_unstake_handler(ctx, user, cancel_rewards)
+    }
+
+    pub fn set_allowlist_root(
+        ctx: Context<SetAllowlistRoot>,
+        allowlist_root: [u8; 32],
+    ) -> Result<()> {
+        instructions::set_allowlist_root::set_allowlist_root_handler(ctx, allowlist_root)
+    }
+
+    pub fn close_stake_account(ctx: Context<CloseStakeAccount>) -> Result<()> {
+        instructions::close_stake_account::close_stake_account_handler(ctx)
+    }
+
+    pub fn fund_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FundRewards<'info>>,
+        amount: u64,
+        compute_runway: bool,
+    ) -> Result<()> {
+        instructions::fund_rewards::fund_rewards_handler(ctx, amount, compute_runway)
+    }
+
+    pub fn get_stake_info(ctx: Context<GetStakeInfo>, user: Pubkey) -> Result<StakeInfoView> {
+        instructions::get_stake_info::get_stake_info_handler(ctx, user)
+    }
+
+    pub fn pending_rewards(ctx: Context<PendingRewards>, user: Pubkey) -> Result<u64> {
+        instructions::pending_rewards::pending_rewards_handler(ctx, user)
+    }
+
+    pub fn stake_for<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StakeFor<'info>>,
+        beneficiary: Pubkey,
+        amount: u64,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::stake_for::stake_for_handler(ctx, beneficiary, amount, allowlist_proof)
+    }
+
+    pub fn set_claim_settings(
+        ctx: Context<SetClaimSettings>,
+        reward_recipient: Option<Pubkey>,
+        claim_delegate: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_claim_settings::set_claim_settings_handler(
+            ctx,
+            reward_recipient,
+            claim_delegate,
+        )
+    }
+
+    pub fn checkpoint_positions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CheckpointPositions<'info>>,
+    ) -> Result<()> {
+        instructions::checkpoint_positions::checkpoint_positions_handler(ctx)
+    }
 }
@@ -1,18 +1,60 @@
+use crate::constants::{MAX_LOCK_TIERS, MAX_REWARD_STREAMS};
 use anchor_lang::prelude::*;
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum RewardMode {
+    /// Every staker earns `reward_rate` basis points of their stake per day
+    FixedRate,
+    /// `reward_per_second` tokens are split across `total_staked` (reward-per-share accumulator)
+    Accumulator,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct GlobalState {
     pub admin: Pubkey,
+    pub pending_admin: Pubkey, // Pubkey::default() when no transfer is pending
     pub staking_mint: Pubkey,
     pub reward_mint: Pubkey,
     pub staking_vault: Pubkey,
     pub reward_vault: Pubkey,
-    pub reward_rate: u64, // Basis points (100 = 1%)
+    pub reward_mode: RewardMode,
+    pub reward_rate: u64,       // Basis points (100 = 1%), FixedRate mode only
+    pub reward_per_second: u64, // Emission budget, Accumulator mode only
     pub total_staked: u64,
+    pub reward_index: u128, // Cumulative reward_rate * seconds up to last_update_time
+    pub acc_reward_per_share: u128, // Scaled by ACC_PRECISION, up to last_update_time
+    pub last_update_time: i64,
+    pub rewards_start: i64, // Rewards only accrue within [rewards_start, rewards_end]
+    pub rewards_end: i64,
+    pub paused: u8, // Bitmask of PAUSE_* flags
+    pub reward_stream_count: u8,
+    pub lock_tiers: [LockTier; MAX_LOCK_TIERS],
+    pub lock_tier_count: u8,
+    pub cooldown_seconds: i64,     // 0 = unstake pays out immediately
+    pub receipt_mint: Pubkey,      // Liquid staking receipt, default pubkey = disabled
+    pub quarantine: Pubkey, // Owner of the accounts force_unstake seizes into, default = unset
+    pub allowlist_root: [u8; 32], // Merkle root of admitted wallets, all zeros = open pool
+    pub total_funded: u64,  // Reward tokens received through fund_rewards
+    pub total_distributed: u64, // Primary rewards paid out of the reward vault
+    pub total_withdrawn: u64, // Reward tokens the admin pulled back out of the vault
+    pub reward_epoch: u32,  // Bumped by every reward rate or campaign window change
+    pub epoch_start_time: i64, // When the current rate and window took effect
+    pub epoch_start_index: u128, // reward_index at epoch_start_time
+    pub previous_reward_rate: u64, // Rate and window of the previous epoch, for stale positions
+    pub previous_rewards_start: i64,
+    pub previous_rewards_end: i64,
+    pub boosted_positions: u32, // Lock-boosted positions checkpointed in the current epoch
+    pub stale_boosted_positions: u32, // Lock-boosted positions last checkpointed in the previous epoch
     pub bump: u8,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct LockTier {
+    pub duration: i64,       // Seconds the stake is locked for
+    pub multiplier_bps: u16, // Reward boost, 10_000 = 1x
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct UserStakeInfo {
@@ -20,7 +62,39 @@ pub struct UserStakeInfo {
     pub amount: u64,
     pub stake_timestamp: i64,
     pub last_claim_time: i64,
-    pub reward_debt: u64,
+    pub reward_debt: u64, // amount * acc_reward_per_share at the last settlement
+    pub total_claimed: u64,
+    pub reward_index: u128, // GlobalState.reward_index at the last settlement
+    pub last_checkpoint_time: i64, // When reward_index was last checkpointed
+    pub stream_rewards: [StreamCheckpoint; MAX_REWARD_STREAMS], // Indexed by RewardStream.index
+    pub lock_until: i64,
+    pub multiplier_bps: u16, // Boost on primary rewards while locked, 10_000 = 1x
+    pub unbonding_amount: u64, // Requested for withdrawal, no longer earning rewards
+    pub unbonding_available_at: i64,
+    pub allowlist_verified: bool, // Proof accepted once, later stakes skip it
+    pub unpaid_rewards: u64,      // Owed but not covered by the reward vault at payout
+    pub reward_recipient: Option<Pubkey>, // claim_rewards pays here instead of the owner
+    pub claim_delegate: Option<Pubkey>, // May call claim_rewards on the owner's behalf
+    pub boost_epoch: Option<u32>, // Reward epoch whose boosted_positions count includes this stake
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct StreamCheckpoint {
+    pub reward_debt: u64, // amount * stream.acc_reward_per_share at the last settlement
+    pub unclaimed: u64,   // Settled but not yet paid out
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RewardStream {
+    pub state: Pubkey,
+    pub index: u8,
+    pub reward_mint: Pubkey,
+    pub reward_vault: Pubkey,
+    pub reward_per_second: u64, // Emission budget split across GlobalState.total_staked
+    pub acc_reward_per_share: u128, // Scaled by ACC_PRECISION, up to last_update_time
+    pub last_update_time: i64,
     pub bump: u8,
 }
 
@@ -29,5 +103,24 @@ pub struct UserStakeInfo {
 pub struct BlacklistEntry {
     pub address: Pubkey,
     pub added_at: i64,
+    pub reason_code: u16, // Compliance reason, meaning defined off-chain
+    pub reference: Option<[u8; 32]>, // Hash of the case reference in the compliance system
+    pub expires_at: Option<i64>, // Entry stops blocking at this time, None = permanent
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum Role {
+    /// May add and remove blacklist entries, nothing else
+    BlacklistManager,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RoleAssignment {
+    pub state: Pubkey,
+    pub role: Role,
+    pub member: Pubkey,
+    pub granted_at: i64,
     pub bump: u8,
 }
@@ -1,99 +1,552 @@
 use crate::constants::*;
 use crate::errors::StakingError;
-use crate::state::{GlobalState, UserStakeInfo};
+use crate::events::RewardShortfall;
+use crate::state::{
+    BlacklistEntry, GlobalState, RewardMode, RewardStream, RoleAssignment, UserStakeInfo,
+};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::system_program;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::onchain::invoke_transfer_checked;
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, Burn, Mint, MintTo, TokenAccount, TokenInterface};
 
+#[allow(clippy::too_many_arguments)]
 pub fn claim_pending_rewards<'info>(
-    state: &Account<'info, GlobalState>,
+    state: &mut Account<'info, GlobalState>,
     user_stake: &mut Account<'info, UserStakeInfo>,
-    reward_vault: &Account<'info, TokenAccount>,
-    user_reward_account: &Account<'info, TokenAccount>,
-    token_program: &Program<'info, Token>,
+    reward_vault: &InterfaceAccount<'info, TokenAccount>,
+    reward_mint: &InterfaceAccount<'info, Mint>,
+    user_reward_account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    additional_accounts: &[AccountInfo<'info>],
     clock: &Sysvar<'info, Clock>,
 ) -> Result<u64> {
-    // Calculate rewards from last claim time (or stake time if never claimed)
-    let last_claim = if user_stake.last_claim_time > 0 {
-        user_stake.last_claim_time
-    } else {
-        user_stake.stake_timestamp
-    };
+    // A claim pause halts every payout from the reward vault, not just claim_rewards;
+    // accrued rewards wait in unpaid_rewards until claims resume
+    if state.paused & PAUSE_CLAIM != 0 {
+        accrue_pending_rewards(state, user_stake, clock.unix_timestamp)?;
+        checkpoint_user(state, user_stake, clock.unix_timestamp)?;
+        msg!(
+            "Claims paused, {} rewards recorded as unpaid",
+            user_stake.unpaid_rewards
+        );
+        return Ok(0);
+    }
+
+    let owed = owed_rewards(state, user_stake, clock.unix_timestamp)?;
 
-    let rewards = calculate_rewards(
-        user_stake.amount,
-        last_claim,
-        clock.unix_timestamp,
-        state.reward_rate,
-    )?;
-    
     msg!(
-        "Calculating rewards: amount={}, last_claim={}, current_time={}, rate={}, rewards={}",
+        "Calculating rewards: amount={}, mode={:?}, current_time={}, owed={}",
         user_stake.amount,
-        last_claim,
+        state.reward_mode,
         clock.unix_timestamp,
-        state.reward_rate,
-        rewards
+        owed
     );
 
+    // Pay what the vault can cover so an underfunded vault never blocks the caller
+    let rewards = owed.min(reward_vault.amount);
+    user_stake.unpaid_rewards = owed - rewards;
+    if user_stake.unpaid_rewards > 0 {
+        msg!(
+            "Reward vault short by {}, recorded as unpaid",
+            user_stake.unpaid_rewards
+        );
+
+        // Emit event
+        emit!(RewardShortfall {
+            user: user_stake.owner,
+            owed,
+            paid: rewards,
+            unpaid: user_stake.unpaid_rewards,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
     if rewards > 0 {
         // Transfer rewards from reward vault to user
-        let seeds = &[STATE_SEED.as_ref(), state.staking_mint.as_ref(), &[state.bump]];
+        let seeds = &[STATE_SEED, state.staking_mint.as_ref(), &[state.bump]];
         let signer = &[&seeds[..]];
 
-        let cpi_accounts = Transfer {
-            from: reward_vault.to_account_info(),
-            to: user_reward_account.to_account_info(),
-            authority: state.to_account_info(),
-        };
-        let cpi_program = token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, rewards)?;
+        transfer_checked_with_hook(
+            token_program,
+            reward_vault.to_account_info(),
+            reward_mint,
+            user_reward_account.to_account_info(),
+            state.to_account_info(),
+            additional_accounts,
+            rewards,
+            signer,
+        )?;
 
         // Update user stake info
-        user_stake.reward_debt = user_stake
-            .reward_debt
+        user_stake.total_claimed = user_stake
+            .total_claimed
+            .checked_add(rewards)
+            .ok_or(StakingError::ArithmeticOverflow)?;
+        state.total_distributed = state
+            .total_distributed
             .checked_add(rewards)
             .ok_or(StakingError::ArithmeticOverflow)?;
         user_stake.last_claim_time = clock.unix_timestamp;
     }
 
+    checkpoint_user(state, user_stake, clock.unix_timestamp)?;
+
     Ok(rewards)
 }
 
-pub fn calculate_rewards(
+/// Accounts `deposit_stake` uses besides the pool state and the position.
+pub struct DepositAccounts<'a, 'info> {
+    pub depositor: &'a Signer<'info>,
+    pub depositor_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub staking_mint: &'a InterfaceAccount<'info, Mint>,
+    pub staking_vault: &'a mut InterfaceAccount<'info, TokenAccount>,
+    pub reward_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub reward_mint: &'a InterfaceAccount<'info, Mint>,
+    pub reward_account: &'a InterfaceAccount<'info, TokenAccount>, // Receives the owner's pending rewards
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    pub receipt_mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    pub receipt_account: Option<&'a InterfaceAccount<'info, TokenAccount>>, // Owner's
+    pub receipt_token_program: Option<&'a Program<'info, Token2022>>,
+    pub clock: &'a Sysvar<'info, Clock>,
+}
+
+/// Moves `amount` staking tokens from the depositor into `owner`'s position, for
+/// `stake` and `stake_for`. Admits the owner through the allowlist, pays out their
+/// pending rewards first and credits only what the vault received. Returns the
+/// credited amount; the caller checks pause and blacklist state.
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_stake<'info>(
+    state: &mut Account<'info, GlobalState>,
+    user_stake: &mut Account<'info, UserStakeInfo>,
+    owner: Pubkey,
+    accounts: DepositAccounts<'_, 'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
     amount: u64,
-    start_timestamp: i64,
-    end_timestamp: i64,
-    reward_rate: u64,
+    lock_tier: Option<u8>,
+    allowlist_proof: &[[u8; 32]],
 ) -> Result<u64> {
-    let duration = (end_timestamp - start_timestamp) as u64; // duration in seconds
-    
+    let clock = accounts.clock;
+
+    // Permissioned pools admit a wallet once it proves membership; delisting is done
+    // through the blacklist, not by rotating the root. The allowlist gates the
+    // position's owner, not whoever pays for it
+    if state.allowlist_root != [0; 32] && !user_stake.allowlist_verified {
+        require!(
+            verify_allowlist_proof(&state.allowlist_root, &owner, allowlist_proof),
+            StakingError::NotAllowlisted
+        );
+        user_stake.allowlist_verified = true;
+    }
+
+    // Checkpoint global and per-stream accrual before total_staked changes
+    update_reward_index(state, clock.unix_timestamp)?;
+    let mut reward_streams = load_reward_streams(state, remaining_accounts)?;
+    settle_reward_streams(state, &mut reward_streams, user_stake, clock.unix_timestamp)?;
+
+    // If the owner already has a stake, claim rewards first
+    if user_stake.amount > 0 {
+        claim_pending_rewards(
+            state,
+            user_stake,
+            accounts.reward_vault,
+            accounts.reward_mint,
+            accounts.reward_account,
+            accounts.token_program,
+            remaining_accounts,
+            clock,
+        )?;
+    }
+
+    // Transfer staking tokens from the depositor to the vault
+    let vault_balance_before = accounts.staking_vault.amount;
+    transfer_checked_with_hook(
+        accounts.token_program,
+        accounts.depositor_token_account.to_account_info(),
+        accounts.staking_mint,
+        accounts.staking_vault.to_account_info(),
+        accounts.depositor.to_account_info(),
+        remaining_accounts,
+        amount,
+        &[],
+    )?;
+
+    // Credit what the vault actually received; transfer-fee mints withhold part of it
+    accounts.staking_vault.reload()?;
+    let amount = accounts
+        .staking_vault
+        .amount
+        .checked_sub(vault_balance_before)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    require!(amount > 0, StakingError::InvalidStakeAmount);
+
+    mint_receipt(
+        state,
+        accounts.receipt_mint,
+        accounts.receipt_account,
+        accounts.receipt_token_program,
+        amount,
+    )?;
+
+    // Update user stake info
+    user_stake.owner = owner;
+    user_stake.amount = user_stake
+        .amount
+        .checked_add(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    // Only update stake_timestamp on first stake
+    if user_stake.stake_timestamp == 0 {
+        user_stake.stake_timestamp = clock.unix_timestamp;
+    }
+
+    // Locking extends the whole position; never shorten an existing lock or reduce its boost
+    if let Some(tier_index) = lock_tier {
+        require!(
+            tier_index < state.lock_tier_count,
+            StakingError::InvalidLockTier
+        );
+        let tier = state.lock_tiers[tier_index as usize];
+        let lock_until = clock
+            .unix_timestamp
+            .checked_add(tier.duration)
+            .ok_or(StakingError::ArithmeticOverflow)?;
+        user_stake.lock_until = user_stake.lock_until.max(lock_until);
+        user_stake.multiplier_bps = user_stake.multiplier_bps.max(tier.multiplier_bps);
+    }
+
+    // New amount accrues from the current indices
+    checkpoint_user(state, user_stake, clock.unix_timestamp)?;
+    checkpoint_reward_streams(&reward_streams, user_stake)?;
+
+    // Update global state
+    state.total_staked = state
+        .total_staked
+        .checked_add(amount)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    Ok(amount)
+}
+
+/// Rewards accrued by `user_stake` since its last checkpoint, as of `now`.
+pub fn pending_rewards(state: &GlobalState, user_stake: &UserStakeInfo, now: i64) -> Result<u64> {
+    match state.reward_mode {
+        RewardMode::FixedRate => {
+            // Rewards are owed for the rate accrued since the user's last settlement,
+            // so rate changes in between are honoured period by period
+            let accrued_index = current_reward_index(state, now)?
+                .checked_sub(user_stake.reward_index)
+                .ok_or(StakingError::ArithmeticOverflow)?;
+
+            // The lock boost only covers the part of the period before lock_until; the
+            // index is read at that instant so rate and window changes land on the right side
+            let boost_end = now.min(user_stake.lock_until);
+            let boosted_index = if user_stake.multiplier_bps > BPS_DENOMINATOR
+                && boost_end > user_stake.last_checkpoint_time
+            {
+                reward_index_at(state, user_stake, boost_end)?
+                    .checked_sub(user_stake.reward_index)
+                    .ok_or(StakingError::ArithmeticOverflow)?
+                    .min(accrued_index)
+            } else {
+                0
+            };
+
+            let boosted =
+                calculate_rewards(user_stake.amount, boosted_index, user_stake.multiplier_bps)?;
+            let base = calculate_rewards(
+                user_stake.amount,
+                accrued_index - boosted_index,
+                BPS_DENOMINATOR,
+            )?;
+            Ok(boosted
+                .checked_add(base)
+                .ok_or(StakingError::ArithmeticOverflow)?)
+        }
+        RewardMode::Accumulator => {
+            let accumulated =
+                accumulated_rewards(user_stake.amount, current_acc_reward_per_share(state, now)?)?;
+            Ok(accumulated
+                .checked_sub(user_stake.reward_debt)
+                .ok_or(StakingError::ArithmeticOverflow)?)
+        }
+    }
+}
+
+/// Gross runway: seconds `vault_balance` lasts at the pool's current base emission
+/// (lock boosts and the campaign window are ignored). Rewards already owed to stakers
+/// (`unpaid_rewards` and accrual since their last checkpoint) are not tracked pool-wide
+/// and are not subtracted, so the real runway is shorter. `None` when nothing is being
+/// emitted.
+pub fn reward_runway_seconds(state: &GlobalState, vault_balance: u64) -> Option<u64> {
+    let runway = match state.reward_mode {
+        // total_staked * reward_rate / (86400 * 10000) tokens per second
+        RewardMode::FixedRate => (vault_balance as u128)
+            .checked_mul(86400u128 * 10000u128)?
+            .checked_div((state.total_staked as u128).checked_mul(state.reward_rate as u128)?)?,
+        RewardMode::Accumulator => {
+            (vault_balance as u128).checked_div(state.reward_per_second as u128)?
+        }
+    };
+    Some(u64::try_from(runway).unwrap_or(u64::MAX))
+}
+
+/// Moves rewards accrued since the last checkpoint into `unpaid_rewards`, for paths
+/// that cannot pay out. Call `checkpoint_user` afterwards.
+pub fn accrue_pending_rewards(
+    state: &GlobalState,
+    user_stake: &mut UserStakeInfo,
+    now: i64,
+) -> Result<()> {
+    user_stake.unpaid_rewards = owed_rewards(state, user_stake, now)?;
+    Ok(())
+}
+
+/// Pending rewards plus any shortfall left over from earlier payouts.
+pub fn owed_rewards(state: &GlobalState, user_stake: &UserStakeInfo, now: i64) -> Result<u64> {
+    Ok(pending_rewards(state, user_stake, now)?
+        .checked_add(user_stake.unpaid_rewards)
+        .ok_or(StakingError::ArithmeticOverflow)?)
+}
+
+/// Deserializes a stake PDA that may not have been created yet.
+pub fn load_user_stake(user_stake_info: &AccountInfo) -> Result<Option<UserStakeInfo>> {
+    if user_stake_info.owner != &crate::ID || user_stake_info.data_is_empty() {
+        return Ok(None);
+    }
+    let user_stake = UserStakeInfo::try_deserialize(&mut &user_stake_info.try_borrow_data()?[..])?;
+    Ok(Some(user_stake))
+}
+
+/// Resets the user's reward checkpoints to the current global indices.
+/// Call after settling rewards and after every change to `user_stake.amount`.
+pub fn checkpoint_user(
+    state: &mut GlobalState,
+    user_stake: &mut UserStakeInfo,
+    now: i64,
+) -> Result<()> {
+    // Release the boosted slot taken at the previous checkpoint; a position from
+    // before the last rate change can only belong to the previous epoch
+    if let Some(boost_epoch) = user_stake.boost_epoch.take() {
+        if boost_epoch == state.reward_epoch {
+            state.boosted_positions = state.boosted_positions.saturating_sub(1);
+        } else {
+            state.stale_boosted_positions = state.stale_boosted_positions.saturating_sub(1);
+        }
+    }
+
+    // A lock boost only carries into periods that start while the stake is still locked
+    if user_stake.multiplier_bps == 0 || user_stake.lock_until <= now {
+        user_stake.multiplier_bps = BPS_DENOMINATOR;
+    }
+
+    // Boosted positions are counted so a rate change can wait until every one of
+    // them has been checkpointed under the previous rate (see begin_reward_epoch)
+    if state.reward_mode == RewardMode::FixedRate
+        && user_stake.multiplier_bps > BPS_DENOMINATOR
+        && user_stake.amount > 0
+    {
+        user_stake.boost_epoch = Some(state.reward_epoch);
+        state.boosted_positions = state
+            .boosted_positions
+            .checked_add(1)
+            .ok_or(StakingError::ArithmeticOverflow)?;
+    }
+
+    user_stake.reward_index = current_reward_index(state, now)?;
+    user_stake.last_checkpoint_time = now;
+    user_stake.reward_debt =
+        accumulated_rewards(user_stake.amount, current_acc_reward_per_share(state, now)?)?;
+    Ok(())
+}
+
+/// Forgets the timing and reward debt of a fully exited position, as the EVM
+/// contract does. Call after the final checkpoints; unclaimed stream rewards are kept.
+pub fn reset_empty_position(user_stake: &mut UserStakeInfo) {
+    if user_stake.amount == 0 {
+        user_stake.stake_timestamp = 0;
+        user_stake.last_claim_time = 0;
+        user_stake.reward_debt = 0;
+    }
+}
+
+/// Returns the global reward index (cumulative `reward_rate * seconds`) as of `now`,
+/// without writing it back to the state.
+pub fn current_reward_index(state: &GlobalState, now: i64) -> Result<u128> {
+    if state.reward_mode != RewardMode::FixedRate {
+        return Ok(state.reward_index);
+    }
+
+    let elapsed = active_reward_seconds(state, now);
+
+    let accrued = (state.reward_rate as u128)
+        .checked_mul(elapsed)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    Ok(state
+        .reward_index
+        .checked_add(accrued)
+        .ok_or(StakingError::ArithmeticOverflow)?)
+}
+
+/// Returns the reward-per-share accumulator as of `now`, without writing it back
+/// to the state. Emissions while nothing is staked are not distributed.
+pub fn current_acc_reward_per_share(state: &GlobalState, now: i64) -> Result<u128> {
+    if state.reward_mode != RewardMode::Accumulator {
+        return Ok(state.acc_reward_per_share);
+    }
+
+    accumulate_per_share(
+        state.acc_reward_per_share,
+        state.reward_per_second,
+        active_reward_seconds(state, now),
+        state.total_staked,
+    )
+}
+
+/// Adds `reward_per_second` emissions over `elapsed` seconds, split across
+/// `total_staked`, to a reward-per-share accumulator.
+pub fn accumulate_per_share(
+    acc_reward_per_share: u128,
+    reward_per_second: u64,
+    elapsed: u128,
+    total_staked: u64,
+) -> Result<u128> {
+    if total_staked == 0 {
+        return Ok(acc_reward_per_share);
+    }
+
+    let emitted = (reward_per_second as u128)
+        .checked_mul(elapsed)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    let per_share = emitted
+        .checked_mul(ACC_PRECISION)
+        .ok_or(StakingError::ArithmeticOverflow)?
+        .checked_div(total_staked as u128)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
+    Ok(acc_reward_per_share
+        .checked_add(per_share)
+        .ok_or(StakingError::ArithmeticOverflow)?)
+}
+
+/// Seconds of `[last_update_time, now]` that fall inside the reward campaign window.
+pub fn active_reward_seconds(state: &GlobalState, now: i64) -> u128 {
+    campaign_seconds(state, state.last_update_time, now)
+}
+
+/// Seconds of `[from, now]` that fall inside the reward campaign window.
+pub fn campaign_seconds(state: &GlobalState, from: i64, now: i64) -> u128 {
+    window_seconds(state.rewards_start, state.rewards_end, from, now)
+}
+
+/// Seconds of `[from, to]` that fall inside `[start, end]`.
+pub fn window_seconds(start: i64, end: i64, from: i64, to: i64) -> u128 {
+    let from = from.max(start);
+    let to = to.min(end);
+    to.saturating_sub(from).max(0) as u128
+}
+
+/// Global reward index at `at`, a moment between the user's last checkpoint and
+/// now. FixedRate mode only.
+pub fn reward_index_at(state: &GlobalState, user_stake: &UserStakeInfo, at: i64) -> Result<u128> {
+    // Before the current epoch only stale positions are read here, and those were
+    // checkpointed under the previous epoch's rate and window
+    let (base_index, base_time, rate, start, end) = if at >= state.epoch_start_time {
+        (
+            state.epoch_start_index,
+            state.epoch_start_time,
+            state.reward_rate,
+            state.rewards_start,
+            state.rewards_end,
+        )
+    } else {
+        (
+            user_stake.reward_index,
+            user_stake.last_checkpoint_time,
+            state.previous_reward_rate,
+            state.previous_rewards_start,
+            state.previous_rewards_end,
+        )
+    };
+
+    let accrued = (rate as u128)
+        .checked_mul(window_seconds(start, end, base_time, at))
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    Ok(base_index
+        .checked_add(accrued)
+        .ok_or(StakingError::ArithmeticOverflow)?)
+}
+
+/// Starts a new reward epoch ahead of a reward rate or campaign window change. Call
+/// after `update_reward_index` and before changing either. Every lock-boosted
+/// position from the previous epoch must have been checkpointed since, so no
+/// position ever spans more than one change.
+pub fn begin_reward_epoch(state: &mut GlobalState, now: i64) -> Result<()> {
+    require!(
+        state.stale_boosted_positions == 0,
+        StakingError::StaleBoostedPositions
+    );
+
+    state.previous_reward_rate = state.reward_rate;
+    state.previous_rewards_start = state.rewards_start;
+    state.previous_rewards_end = state.rewards_end;
+    state.epoch_start_time = now;
+    state.epoch_start_index = state.reward_index;
+    state.reward_epoch = state
+        .reward_epoch
+        .checked_add(1)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    state.stale_boosted_positions = state.boosted_positions;
+    state.boosted_positions = 0;
+    Ok(())
+}
+
+/// Checkpoints the global reward indices at `now`. Must be called before any
+/// change to `reward_rate`, `total_staked` or the campaign window so the old
+/// values apply up to this moment.
+pub fn update_reward_index(state: &mut GlobalState, now: i64) -> Result<()> {
+    state.reward_index = current_reward_index(state, now)?;
+    state.acc_reward_per_share = current_acc_reward_per_share(state, now)?;
+    state.last_update_time = now;
+    Ok(())
+}
+
+/// `amount * acc_reward_per_share`, descaled to token units.
+pub fn accumulated_rewards(amount: u64, acc_reward_per_share: u128) -> Result<u64> {
+    let rewards = (amount as u128)
+        .checked_mul(acc_reward_per_share)
+        .ok_or(StakingError::ArithmeticOverflow)?
+        / ACC_PRECISION;
+
+    Ok(u64::try_from(rewards).map_err(|_| StakingError::ArithmeticOverflow)?)
+}
+
+pub fn calculate_rewards(amount: u64, accrued_index: u128, multiplier_bps: u16) -> Result<u64> {
     msg!(
-        "calculate_rewards: amount={}, start={}, end={}, duration={}, rate={}",
+        "calculate_rewards: amount={}, accrued_index={}, multiplier_bps={}",
         amount,
-        start_timestamp,
-        end_timestamp,
-        duration,
-        reward_rate
+        accrued_index,
+        multiplier_bps
     );
 
     // Calculate rewards based on seconds to match EVM implementation
-    // Formula: (amount * rate * time_in_seconds) / (seconds_per_day * precision)
+    // Formula: (amount * sum(rate * time_in_seconds) * multiplier) / (seconds_per_day * precision * multiplier_precision)
     // This ensures continuous rewards calculation without losing partial days
 
     // Use u128 for intermediate calculations to avoid overflow
-    // First multiply amount by rate
-    let amount_rate = (amount as u128)
-        .checked_mul(reward_rate as u128)
+    let numerator = (amount as u128)
+        .checked_mul(accrued_index)
+        .ok_or(StakingError::ArithmeticOverflow)?
+        .checked_mul(multiplier_bps as u128)
         .ok_or(StakingError::ArithmeticOverflow)?;
 
-    // Then multiply by duration
-    let numerator = amount_rate
-        .checked_mul(duration as u128)
-        .ok_or(StakingError::ArithmeticOverflow)?;
-
-    // Finally divide by (seconds_per_day * precision)
-    let denominator = 86400u128 * 10000u128; // 86400 seconds per day * 10000 basis points
+    // Divide by (seconds_per_day * precision * multiplier_precision)
+    let denominator = 86400u128 * 10000u128 * BPS_DENOMINATOR as u128; // 86400 seconds per day * 10000 basis points * 1x boost
     let rewards = numerator
         .checked_div(denominator)
         .ok_or(StakingError::ArithmeticOverflow)?;
@@ -101,3 +554,339 @@ pub fn calculate_rewards(
     // Convert back to u64, checking for overflow
     Ok(u64::try_from(rewards).map_err(|_| StakingError::ArithmeticOverflow)?)
 }
+
+/// Deserializes the pool's reward streams from `remaining_accounts`. Every registered
+/// stream must be passed, writable and in index order, whenever a stake amount changes.
+pub fn load_reward_streams<'info>(
+    state: &Account<'info, GlobalState>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<Account<'info, RewardStream>>> {
+    let count = state.reward_stream_count as usize;
+    require!(
+        remaining_accounts.len() >= count,
+        StakingError::MissingRewardStreams
+    );
+
+    let mut streams = Vec::with_capacity(count);
+    for (index, account_info) in remaining_accounts.iter().take(count).enumerate() {
+        require!(account_info.is_writable, StakingError::InvalidRewardStream);
+        let stream = Account::<RewardStream>::try_from(account_info)?;
+        require!(
+            stream.state == state.key() && stream.index as usize == index,
+            StakingError::InvalidRewardStream
+        );
+        streams.push(stream);
+    }
+
+    Ok(streams)
+}
+
+/// Brings `stream`'s accumulator up to `now`. Streams emit only inside the pool's
+/// campaign window, like the primary rewards.
+pub fn update_reward_stream(
+    state: &GlobalState,
+    stream: &mut RewardStream,
+    now: i64,
+) -> Result<()> {
+    stream.acc_reward_per_share = accumulate_per_share(
+        stream.acc_reward_per_share,
+        stream.reward_per_second,
+        campaign_seconds(state, stream.last_update_time, now),
+        state.total_staked,
+    )?;
+    stream.last_update_time = now;
+    Ok(())
+}
+
+/// Brings `stream` up to `now` and moves the user's accrued share into its
+/// `unclaimed` balance. Must run before `user_stake.amount` or `total_staked` change.
+pub fn settle_reward_stream(
+    state: &GlobalState,
+    stream: &mut RewardStream,
+    user_stake: &mut UserStakeInfo,
+    now: i64,
+) -> Result<()> {
+    update_reward_stream(state, stream, now)?;
+
+    let checkpoint = &mut user_stake.stream_rewards[stream.index as usize];
+    let accrued = accumulated_rewards(user_stake.amount, stream.acc_reward_per_share)?
+        .checked_sub(checkpoint.reward_debt)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    checkpoint.unclaimed = checkpoint
+        .unclaimed
+        .checked_add(accrued)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    checkpoint.reward_debt = accumulated_rewards(user_stake.amount, stream.acc_reward_per_share)?;
+
+    Ok(())
+}
+
+/// Settles every stream for the user before an amount change.
+pub fn settle_reward_streams(
+    state: &GlobalState,
+    streams: &mut [Account<RewardStream>],
+    user_stake: &mut UserStakeInfo,
+    now: i64,
+) -> Result<()> {
+    for stream in streams.iter_mut() {
+        settle_reward_stream(state, stream, user_stake, now)?;
+    }
+    Ok(())
+}
+
+/// Re-bases the user's stream debts on their new amount and persists the streams.
+pub fn checkpoint_reward_streams(
+    streams: &[Account<RewardStream>],
+    user_stake: &mut UserStakeInfo,
+) -> Result<()> {
+    for stream in streams {
+        user_stake.stream_rewards[stream.index as usize].reward_debt =
+            accumulated_rewards(user_stake.amount, stream.acc_reward_per_share)?;
+        stream.exit(&crate::ID)?;
+    }
+    Ok(())
+}
+
+/// Mints receipt tokens 1:1 for principal entering the pool. No-op when the pool
+/// has no receipt mint.
+pub fn mint_receipt<'info>(
+    state: &Account<'info, GlobalState>,
+    receipt_mint: Option<&InterfaceAccount<'info, Mint>>,
+    receipt_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    receipt_token_program: Option<&Program<'info, Token2022>>,
+    amount: u64,
+) -> Result<()> {
+    if state.receipt_mint == Pubkey::default() {
+        return Ok(());
+    }
+    let (Some(receipt_mint), Some(receipt_account), Some(receipt_token_program)) =
+        (receipt_mint, receipt_account, receipt_token_program)
+    else {
+        return err!(StakingError::MissingReceiptAccounts);
+    };
+
+    let seeds = &[STATE_SEED, state.staking_mint.as_ref(), &[state.bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = MintTo {
+        mint: receipt_mint.to_account_info(),
+        to: receipt_account.to_account_info(),
+        authority: state.to_account_info(),
+    };
+    let cpi_program = receipt_token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token_interface::mint_to(cpi_ctx, amount)
+}
+
+/// Burns the owner's receipt tokens for principal leaving the active stake. No-op
+/// when the pool has no receipt mint.
+pub fn burn_receipt<'info>(
+    state: &Account<'info, GlobalState>,
+    receipt_mint: Option<&InterfaceAccount<'info, Mint>>,
+    receipt_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    owner: &Signer<'info>,
+    receipt_token_program: Option<&Program<'info, Token2022>>,
+    amount: u64,
+) -> Result<()> {
+    if state.receipt_mint == Pubkey::default() {
+        return Ok(());
+    }
+    let (Some(receipt_mint), Some(receipt_account), Some(receipt_token_program)) =
+        (receipt_mint, receipt_account, receipt_token_program)
+    else {
+        return err!(StakingError::MissingReceiptAccounts);
+    };
+
+    let cpi_accounts = Burn {
+        mint: receipt_mint.to_account_info(),
+        from: receipt_account.to_account_info(),
+        authority: owner.to_account_info(),
+    };
+    let cpi_program = receipt_token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    token_interface::burn(cpi_ctx, amount)
+}
+
+/// Rejects Token-2022 mints whose extensions would let a third party move tokens
+/// out of a pool vault, or stop the vault from paying them out.
+pub fn validate_mint_extensions(mint: &InterfaceAccount<Mint>) -> Result<()> {
+    let mint_info = mint.to_account_info();
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&mint_data)?;
+
+    for extension in mint_state.get_extension_types()? {
+        require!(
+            !matches!(
+                extension,
+                ExtensionType::PermanentDelegate | ExtensionType::NonTransferable
+            ),
+            StakingError::UnsupportedMintExtension
+        );
+    }
+
+    Ok(())
+}
+
+/// `transfer_checked` CPI that also forwards the accounts a Token-2022 transfer hook
+/// on `mint` needs. They are looked up by key in `additional_accounts`, so callers
+/// append them to the instruction's remaining accounts (after any reward streams).
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked_with_hook<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    from: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    additional_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_transfer_checked(
+        token_program.key,
+        from,
+        mint.to_account_info(),
+        to,
+        authority,
+        additional_accounts,
+        amount,
+        mint.decimals,
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Creates a program-owned PDA outside of an `init` constraint. Like `init`, it
+/// tolerates lamports sent to the address beforehand, so a pre-funded PDA cannot
+/// block creation.
+pub fn create_pda_account<'info>(
+    payer: &Signer<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let rent_exempt = Rent::get()?.minimum_balance(space);
+    let current_lamports = account.lamports();
+
+    if current_lamports == 0 {
+        let cpi_accounts = system_program::CreateAccount {
+            from: payer.to_account_info(),
+            to: account.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        return system_program::create_account(cpi_ctx, rent_exempt, space as u64, &crate::ID);
+    }
+
+    let top_up = rent_exempt.saturating_sub(current_lamports);
+    if top_up > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: payer.to_account_info(),
+            to: account.clone(),
+        };
+        let cpi_ctx = CpiContext::new(system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, top_up)?;
+    }
+
+    let cpi_accounts = system_program::Allocate {
+        account_to_allocate: account.clone(),
+    };
+    let cpi_ctx =
+        CpiContext::new_with_signer(system_program.to_account_info(), cpi_accounts, signer_seeds);
+    system_program::allocate(cpi_ctx, space as u64)?;
+
+    let cpi_accounts = system_program::Assign {
+        account_to_assign: account.clone(),
+    };
+    let cpi_ctx =
+        CpiContext::new_with_signer(system_program.to_account_info(), cpi_accounts, signer_seeds);
+    system_program::assign(cpi_ctx, &crate::ID)
+}
+
+/// Closes a program-owned account into `destination`, the same way the `close`
+/// constraint does.
+pub fn close_pda_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let destination_lamports = destination.lamports();
+    **destination.try_borrow_mut_lamports()? = destination_lamports
+        .checked_add(account.lamports())
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+
+    account.assign(&system_program::ID);
+    account.realloc(0, false)?;
+    Ok(())
+}
+
+/// The pool admin always manages the blacklist; anyone else needs a `BlacklistManager`
+/// role assignment, whose PDA seeds are checked by the accounts struct.
+pub fn require_blacklist_authority(
+    state: &GlobalState,
+    authority: &Signer,
+    blacklist_manager: &Option<Box<Account<RoleAssignment>>>,
+) -> Result<()> {
+    require!(
+        authority.key() == state.admin || blacklist_manager.is_some(),
+        StakingError::NotBlacklistManager
+    );
+    Ok(())
+}
+
+/// Whether the (possibly uninitialized) blacklist PDA currently blocks its address.
+/// Only a program-owned `BlacklistEntry` counts, so lamports sent to the address
+/// cannot lock its owner out. Entries past their `expires_at` no longer count.
+pub fn is_blacklisted(blacklist_entry: &AccountInfo, now: i64) -> Result<bool> {
+    if blacklist_entry.owner != &crate::ID || blacklist_entry.data_is_empty() {
+        return Ok(false);
+    }
+
+    let entry = BlacklistEntry::try_deserialize(&mut &blacklist_entry.try_borrow_data()?[..])?;
+    Ok(match entry.expires_at {
+        Some(expires_at) => now < expires_at,
+        None => true,
+    })
+}
+
+/// Rejects payouts to a blacklisted reward recipient. The recipient's entry is only
+/// required while the position routes its rewards away from the owner.
+pub fn require_recipient_not_blacklisted(
+    user_stake: &UserStakeInfo,
+    recipient_blacklist_entry: Option<&AccountInfo>,
+    now: i64,
+) -> Result<()> {
+    let Some(recipient) = user_stake.reward_recipient else {
+        return Ok(());
+    };
+    if recipient == user_stake.owner {
+        return Ok(());
+    }
+
+    let recipient_blacklist_entry =
+        recipient_blacklist_entry.ok_or(StakingError::MissingRecipientBlacklistEntry)?;
+    require!(
+        !is_blacklisted(recipient_blacklist_entry, now)?,
+        StakingError::AddressBlacklisted
+    );
+    Ok(())
+}
+
+/// Checks `proof` against the pool's allowlist root. Leaves are
+/// `sha256(0x00 || wallet)` and nodes `sha256(0x01 || lower || higher)`, with each
+/// pair sorted so proofs carry no left/right flags.
+pub fn verify_allowlist_proof(root: &[u8; 32], wallet: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    let mut node = hashv(&[&[ALLOWLIST_LEAF_PREFIX], wallet.as_ref()]).to_bytes();
+    for sibling in proof {
+        let (lower, higher) = if node <= *sibling {
+            (node, *sibling)
+        } else {
+            (*sibling, node)
+        };
+        node = hashv(&[&[ALLOWLIST_NODE_PREFIX], &lower, &higher]).to_bytes();
+    }
+    node == *root
+}
@@ -187,7 +187,7 @@ async function main() {
       
       try {
         const tx = await program.methods
-          .initialize(new BN(100)) // 1% per day
+          .initialize(new BN(100), { fixedRate: {} }, new BN(0)) // 1% per day
           .accountsPartial({
             admin: wallet.publicKey,
             state: statePda,
@@ -343,10 +343,12 @@ async function main() {
     
     try {
       const tx = await program.methods
-        .stake(stakeAmount)
+        .stake(stakeAmount, null, [])
         .accountsPartial({
           user: wallet.publicKey,
           state: statePda,
+          stakingMint,
+          rewardMint,
           userStakeInfo: userStakeInfoPda,
           userTokenAccount: userStakingAccount,
           stakingVault: stakingVaultPda,
@@ -383,10 +385,13 @@ async function main() {
         .accountsPartial({
           user: wallet.publicKey,
           state: statePda,
+          rewardMint,
           userStakeInfo: userStakeInfoPda,
           userRewardAccount,
           rewardVault: rewardVaultPda,
           blacklistEntry: blacklistPda,
+          recipientBlacklistEntry: blacklistPda,
+          delegateBlacklistEntry: blacklistPda,
           tokenProgram: TOKEN_PROGRAM_ID,
           clock: SYSVAR_CLOCK_PUBKEY,
         })
@@ -415,6 +420,8 @@ async function main() {
         .accountsPartial({
           user: wallet.publicKey,
           state: statePda,
+          stakingMint,
+          rewardMint,
           userStakeInfo: userStakeInfoPda,
           userTokenAccount: userStakingAccount,
           stakingVault: stakingVaultPda,
@@ -449,9 +456,9 @@ async function main() {
       try {
         // Add to blacklist
         const tx = await program.methods
-          .addToBlacklist(testUser.publicKey)
+          .addToBlacklist(testUser.publicKey, 0, null, null)
           .accountsPartial({
-            admin: wallet.publicKey,
+            authority: wallet.publicKey,
             state: statePda,
             blacklistEntry: testBlacklistPda,
             systemProgram: SystemProgram.programId,
@@ -468,7 +475,7 @@ async function main() {
         const removeTx = await program.methods
           .removeFromBlacklist(testUser.publicKey)
           .accountsPartial({
-            admin: wallet.publicKey,
+            authority: wallet.publicKey,
             state: statePda,
             blacklistEntry: testBlacklistPda,
           })
@@ -13,9 +13,11 @@ import {
   createAssociatedTokenAccountIdempotentInstruction,
   createMintToCheckedInstruction,
   createTransferCheckedInstruction,
+  ExtensionType,
   getMintLen,
   getAssociatedTokenAddressSync,
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
   unpackAccount,
 } from "@solana/spl-token";
 import { createKeyPairSignerFromBytes, address, lamports } from "@solana/kit";
@@ -100,20 +102,63 @@ export function createMint(
   return mint.publicKey;
 }
 
+// Token-2022 mint; `extensionInstructions` initialize the given extensions
+// and run before the mint itself is initialized
+export function createToken2022Mint(
+  provider: LiteSVMProvider,
+  payer: Keypair,
+  mintAuthority: PublicKey,
+  decimals: number,
+  extensions: ExtensionType[] = [],
+  extensionInstructions: (mint: PublicKey) => TransactionInstruction[] = () =>
+    []
+): PublicKey {
+  const mint = Keypair.generate();
+  const mintLen = getMintLen(extensions);
+
+  const createAccountIx = SystemProgram.createAccount({
+    fromPubkey: payer.publicKey,
+    newAccountPubkey: mint.publicKey,
+    space: mintLen,
+    lamports: LAMPORTS_PER_SOL,
+    programId: TOKEN_2022_PROGRAM_ID,
+  });
+
+  const initMintIx = createInitializeMint2Instruction(
+    mint.publicKey,
+    decimals,
+    mintAuthority,
+    null,
+    TOKEN_2022_PROGRAM_ID
+  );
+
+  const tx = new Transaction().add(
+    createAccountIx,
+    ...extensionInstructions(mint.publicKey),
+    initMintIx
+  );
+  tx.recentBlockhash = provider.client.latestBlockhash();
+  tx.sign(payer, mint);
+  provider.client.sendTransaction(tx);
+
+  return mint.publicKey;
+}
+
 export function createAssociatedTokenAccount(
   provider: LiteSVMProvider,
   payer: Keypair,
   mint: PublicKey,
-  owner: PublicKey
+  owner: PublicKey,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID
 ): PublicKey {
-  const ata = getAssociatedTokenAddressSync(mint, owner);
+  const ata = getAssociatedTokenAddressSync(mint, owner, false, tokenProgram);
 
   const createAtaIx = createAssociatedTokenAccountIdempotentInstruction(
     payer.publicKey,
     ata,
     owner,
     mint,
-    TOKEN_PROGRAM_ID
+    tokenProgram
   );
 
   const tx = new Transaction().add(createAtaIx);
@@ -130,7 +175,8 @@ export function mintTo(
   mint: PublicKey,
   destination: PublicKey,
   authority: Keypair,
-  amount: bigint
+  amount: bigint,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID
 ): void {
   const mintToIx = createMintToCheckedInstruction(
     mint,
@@ -139,7 +185,7 @@ export function mintTo(
     amount,
     9, // decimals
     [],
-    TOKEN_PROGRAM_ID
+    tokenProgram
   );
 
   const tx = new Transaction().add(mintToIx);
@@ -183,10 +229,11 @@ export function getAccount(provider: LiteSVMProvider, address: PublicKey): any {
     ...accountInfo,
     data: data,
   };
+  // Works for both token programs; the owner tells them apart
   const account = unpackAccount(
     address,
     accountInfoWithBuffer,
-    TOKEN_PROGRAM_ID
+    new PublicKey(accountInfo.owner)
   );
   return account;
 }
@@ -238,6 +285,42 @@ export function getBlacklistEntry(
 }
 
 // PDA helper functions
+export function getPoolPdas(stakingMint: PublicKey) {
+  const [state] = PublicKey.findProgramAddressSync(
+    [Buffer.from("state"), stakingMint.toBuffer()],
+    programId
+  );
+  const [stakingVault] = PublicKey.findProgramAddressSync(
+    [Buffer.from("staking_vault"), state.toBuffer()],
+    programId
+  );
+  const [rewardVault] = PublicKey.findProgramAddressSync(
+    [Buffer.from("reward_vault"), state.toBuffer()],
+    programId
+  );
+  return { state, stakingVault, rewardVault };
+}
+
+export function getRewardStreamPdas(statePda: PublicKey, index: number) {
+  const [rewardStream] = PublicKey.findProgramAddressSync(
+    [Buffer.from("reward_stream"), statePda.toBuffer(), Buffer.from([index])],
+    programId
+  );
+  const [streamVault] = PublicKey.findProgramAddressSync(
+    [Buffer.from("stream_vault"), rewardStream.toBuffer()],
+    programId
+  );
+  return { rewardStream, streamVault };
+}
+
+export function getReceiptMintPda(statePda: PublicKey): PublicKey {
+  const [receiptMint] = PublicKey.findProgramAddressSync(
+    [Buffer.from("receipt_mint"), statePda.toBuffer()],
+    programId
+  );
+  return receiptMint;
+}
+
 export function getUserStakePda(statePda: PublicKey, userPubkey: PublicKey): PublicKey {
   const [pda] = PublicKey.findProgramAddressSync(
     [Buffer.from("stake"), statePda.toBuffer(), userPubkey.toBuffer()],
@@ -254,7 +337,42 @@ export function getBlacklistPda(statePda: PublicKey, userPubkey: PublicKey): Pub
   return pda;
 }
 
+export function getRolePda(
+  statePda: PublicKey,
+  role: programClient.Role,
+  member: PublicKey
+): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("role"),
+      statePda.toBuffer(),
+      Buffer.from([role]),
+      member.toBuffer(),
+    ],
+    programId
+  );
+  return pda;
+}
+
 // Transaction helper functions
+// Appends remaining accounts (e.g. reward streams) to a generated instruction
+export function withRemainingAccounts(
+  instruction: any,
+  remainingAccounts: PublicKey[],
+  writable: boolean = true
+): any {
+  return {
+    ...instruction,
+    accounts: [
+      ...instruction.accounts,
+      ...remainingAccounts.map((pubkey) => ({
+        address: address(pubkey.toBase58()),
+        role: writable ? 1 : 0, // writable or readonly
+      })),
+    ],
+  };
+}
+
 export async function sendTransaction(
   provider: LiteSVMProvider,
   instruction: any,
@@ -279,6 +397,26 @@ export async function sendTransaction(
   return result;
 }
 
+// Same as sendTransaction, for instructions built with @solana/web3.js
+export async function sendWeb3Transaction(
+  provider: LiteSVMProvider,
+  instruction: TransactionInstruction,
+  signers: Keypair[]
+): Promise<any> {
+  provider.client.expireBlockhash();
+
+  const tx = new Transaction().add(instruction);
+  tx.recentBlockhash = provider.client.latestBlockhash();
+  tx.sign(...signers);
+
+  const result = provider.client.sendTransaction(tx);
+  if ("err" in result && typeof result.err === "function") {
+    throw new Error(result.toString());
+  }
+
+  return result;
+}
+
 // User token accounts helper
 export function getUserTokenAccounts(
   userPubkey: PublicKey,
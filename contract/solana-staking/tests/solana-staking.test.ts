@@ -1,28 +1,53 @@
 import { expect } from "chai";
 import { LiteSVM, Clock } from "litesvm";
 import { LiteSVMProvider } from "anchor-litesvm";
-import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
-import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import {
+  createInitializeNonTransferableMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createInitializeTransferHookInstruction,
+  createTransferCheckedInstruction,
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
 import * as fs from "fs";
+import { createHash } from "crypto";
 import { type KeyPairSigner, address } from "@solana/kit";
 import * as programClient from "../dist/js-client";
 import {
+  createAssociatedTokenAccount,
+  createToken2022Mint,
   createTestUser,
+  sendWeb3Transaction,
   setupUserWithTokens,
   sendTransaction,
   getGlobalState,
   getUserStakeInfo,
   getUserStakePda,
   getBlacklistPda,
+  getRolePda,
   createMint,
   mintTo,
   getAccount,
   programId,
   toToken,
   getBlacklistEntry,
+  getPoolPdas,
+  getRewardStreamPdas,
+  getReceiptMintPda,
+  withRemainingAccounts,
 } from "./helper";
 
 const SECONDS_IN_A_DAY = 86400;
+const PAUSE_STAKE = 1;
+const PAUSE_UNSTAKE = 2;
+const PAUSE_CLAIM = 4;
 
 describe("solana-staking", () => {
   let svm: LiteSVM;
@@ -52,6 +77,8 @@ describe("solana-staking", () => {
     const stakeInstruction = programClient.getStakeInstruction({
       user: userSigner,
       state: address(statePda.toBase58()),
+      stakingMint: address(stakingMint.toBase58()),
+      rewardMint: address(rewardMint.toBase58()),
       userStakeInfo: address(userStakePda.toBase58()),
       userTokenAccount: address(stakingToken.toBase58()),
       stakingVault: address(stakingVaultPda.toBase58()),
@@ -60,6 +87,8 @@ describe("solana-staking", () => {
       tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
       blacklistEntry: address(userBlacklistPda.toBase58()),
       amount: amount,
+      lockTier: null,
+      allowlistProof: [],
     });
     return await sendTransaction(provider, stakeInstruction, user);
   }
@@ -78,6 +107,8 @@ describe("solana-staking", () => {
     const unstakeInstruction = programClient.getUnstakeInstruction({
       user: userSigner,
       state: address(statePda.toBase58()),
+      stakingMint: address(stakingMint.toBase58()),
+      rewardMint: address(rewardMint.toBase58()),
       userStakeInfo: address(userStakePda.toBase58()),
       userTokenAccount: address(stakingToken.toBase58()),
       stakingVault: address(stakingVaultPda.toBase58()),
@@ -102,11 +133,14 @@ describe("solana-staking", () => {
     const claimInstruction = programClient.getClaimRewardsInstruction({
       user: userSigner,
       state: address(statePda.toBase58()),
+      rewardMint: address(rewardMint.toBase58()),
       userStakeInfo: address(userStakePda.toBase58()),
       userRewardAccount: address(rewardToken.toBase58()),
       rewardVault: address(rewardVaultPda.toBase58()),
       tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
       blacklistEntry: address(userBlacklistPda.toBase58()),
+      recipientBlacklistEntry: address(userBlacklistPda.toBase58()),
+      delegateBlacklistEntry: address(userBlacklistPda.toBase58()),
     });
     return await sendTransaction(provider, claimInstruction, user);
   }
@@ -115,11 +149,14 @@ describe("solana-staking", () => {
     const blacklistPda = getBlacklistPda(statePda, userToBlacklist);
     const addToBlacklistInstruction =
       programClient.getAddToBlacklistInstruction({
-        admin: adminSigner,
+        authority: adminSigner,
         systemProgram: address(SystemProgram.programId.toBase58()),
         state: address(statePda.toBase58()),
         blacklistEntry: address(blacklistPda.toBase58()),
         address: address(userToBlacklist.toBase58()),
+        reasonCode: 0,
+        reference: null,
+        expiresAt: null,
       });
     return await sendTransaction(provider, addToBlacklistInstruction, admin);
   }
@@ -128,7 +165,7 @@ describe("solana-staking", () => {
     const blacklistPda = getBlacklistPda(statePda, userToRemove);
     const removeFromBlacklistInstruction =
       programClient.getRemoveFromBlacklistInstruction({
-        admin: adminSigner,
+        authority: adminSigner,
         state: address(statePda.toBase58()),
         blacklistEntry: address(blacklistPda.toBase58()),
         address: address(userToRemove.toBase58()),
@@ -140,6 +177,220 @@ describe("solana-staking", () => {
     );
   }
 
+  async function updateRewardRate(
+    signer: Keypair,
+    signerKeyPair: KeyPairSigner,
+    newRate: number
+  ) {
+    const updateRewardRateInstruction =
+      programClient.getUpdateRewardRateInstruction({
+        admin: signerKeyPair,
+        state: address(statePda.toBase58()),
+        newRate,
+      });
+    return await sendTransaction(provider, updateRewardRateInstruction, signer);
+  }
+
+  async function withdrawRewardTokens(
+    signer: Keypair,
+    signerKeyPair: KeyPairSigner,
+    destination: PublicKey,
+    amount: bigint
+  ) {
+    const withdrawInstruction =
+      programClient.getWithdrawRewardTokensInstruction({
+        admin: signerKeyPair,
+        state: address(statePda.toBase58()),
+        rewardMint: address(rewardMint.toBase58()),
+        rewardVault: address(rewardVaultPda.toBase58()),
+        destination: address(destination.toBase58()),
+        tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+        amount,
+      });
+    return await sendTransaction(provider, withdrawInstruction, signer);
+  }
+
+  async function proposeAdmin(
+    signer: Keypair,
+    signerKeyPair: KeyPairSigner,
+    newAdmin: PublicKey
+  ) {
+    const proposeAdminInstruction = programClient.getProposeAdminInstruction({
+      admin: signerKeyPair,
+      state: address(statePda.toBase58()),
+      newAdmin: address(newAdmin.toBase58()),
+    });
+    return await sendTransaction(provider, proposeAdminInstruction, signer);
+  }
+
+  async function acceptAdmin(signer: Keypair, signerKeyPair: KeyPairSigner) {
+    const acceptAdminInstruction = programClient.getAcceptAdminInstruction({
+      pendingAdmin: signerKeyPair,
+      state: address(statePda.toBase58()),
+    });
+    return await sendTransaction(provider, acceptAdminInstruction, signer);
+  }
+
+  async function pause(flags: number) {
+    const pauseInstruction = programClient.getPauseInstruction({
+      admin: adminSigner,
+      state: address(statePda.toBase58()),
+      flags,
+    });
+    return await sendTransaction(provider, pauseInstruction, admin);
+  }
+
+  async function unpause(flags: number) {
+    const unpauseInstruction = programClient.getUnpauseInstruction({
+      admin: adminSigner,
+      state: address(statePda.toBase58()),
+      flags,
+    });
+    return await sendTransaction(provider, unpauseInstruction, admin);
+  }
+
+  // Pool-parameterised variants for tests that set up their own pool
+  type Pool = ReturnType<typeof getPoolPdas>;
+
+  // Mint accounts the program checks against the pool's GlobalState
+  function poolMints(statePda: PublicKey) {
+    const globalState = getGlobalState(provider, statePda)!;
+    return {
+      stakingMint: globalState.stakingMint,
+      rewardMint: globalState.rewardMint,
+    };
+  }
+
+  async function stakeInPool(
+    pool: Pool,
+    user: Keypair,
+    userSigner: KeyPairSigner,
+    stakingToken: PublicKey,
+    rewardToken: PublicKey,
+    amount: bigint,
+    remainingAccounts: PublicKey[] = [],
+    lockTier: number | null = null,
+    allowlistProof: Uint8Array[] = []
+  ) {
+    const stakeInstruction = programClient.getStakeInstruction({
+      user: userSigner,
+      state: address(pool.state.toBase58()),
+      stakingMint: poolMints(pool.state).stakingMint,
+      rewardMint: poolMints(pool.state).rewardMint,
+      userStakeInfo: address(
+        getUserStakePda(pool.state, user.publicKey).toBase58()
+      ),
+      userTokenAccount: address(stakingToken.toBase58()),
+      stakingVault: address(pool.stakingVault.toBase58()),
+      rewardVault: address(pool.rewardVault.toBase58()),
+      userRewardAccount: address(rewardToken.toBase58()),
+      tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+      blacklistEntry: address(
+        getBlacklistPda(pool.state, user.publicKey).toBase58()
+      ),
+      amount,
+      lockTier,
+      allowlistProof,
+    });
+    return await sendTransaction(
+      provider,
+      withRemainingAccounts(stakeInstruction, remainingAccounts),
+      user
+    );
+  }
+
+  async function unstakeInPool(
+    pool: Pool,
+    user: Keypair,
+    userSigner: KeyPairSigner,
+    stakingToken: PublicKey,
+    rewardToken: PublicKey,
+    amount: bigint,
+    remainingAccounts: PublicKey[] = []
+  ) {
+    const unstakeInstruction = programClient.getUnstakeInstruction({
+      user: userSigner,
+      state: address(pool.state.toBase58()),
+      stakingMint: poolMints(pool.state).stakingMint,
+      rewardMint: poolMints(pool.state).rewardMint,
+      userStakeInfo: address(
+        getUserStakePda(pool.state, user.publicKey).toBase58()
+      ),
+      userTokenAccount: address(stakingToken.toBase58()),
+      stakingVault: address(pool.stakingVault.toBase58()),
+      rewardVault: address(pool.rewardVault.toBase58()),
+      userRewardAccount: address(rewardToken.toBase58()),
+      tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+      blacklistEntry: address(
+        getBlacklistPda(pool.state, user.publicKey).toBase58()
+      ),
+      amount,
+    });
+    return await sendTransaction(
+      provider,
+      withRemainingAccounts(unstakeInstruction, remainingAccounts),
+      user
+    );
+  }
+
+  async function claimInPool(
+    pool: Pool,
+    user: Keypair,
+    userSigner: KeyPairSigner,
+    rewardToken: PublicKey
+  ) {
+    const claimInstruction = programClient.getClaimRewardsInstruction({
+      user: userSigner,
+      state: address(pool.state.toBase58()),
+      rewardMint: poolMints(pool.state).rewardMint,
+      userStakeInfo: address(
+        getUserStakePda(pool.state, user.publicKey).toBase58()
+      ),
+      userRewardAccount: address(rewardToken.toBase58()),
+      rewardVault: address(pool.rewardVault.toBase58()),
+      tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+      blacklistEntry: address(
+        getBlacklistPda(pool.state, user.publicKey).toBase58()
+      ),
+      recipientBlacklistEntry: address(
+        getBlacklistPda(pool.state, user.publicKey).toBase58()
+      ),
+      delegateBlacklistEntry: address(
+        getBlacklistPda(pool.state, user.publicKey).toBase58()
+      ),
+    });
+    return await sendTransaction(provider, claimInstruction, user);
+  }
+
+  async function initializePool(
+    pool: Pool,
+    poolStakingMint: PublicKey,
+    poolRewardMint: PublicKey,
+    rewardRate: number = 500
+  ) {
+    const initializeInstruction = programClient.getInitializeInstruction({
+      admin: adminSigner,
+      state: address(pool.state.toBase58()),
+      stakingMint: address(poolStakingMint.toBase58()),
+      rewardMint: address(poolRewardMint.toBase58()),
+      stakingVault: address(pool.stakingVault.toBase58()),
+      rewardVault: address(pool.rewardVault.toBase58()),
+      tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+      rewardRate,
+      rewardMode: programClient.RewardMode.FixedRate,
+      rewardPerSecond: 0,
+    });
+    await sendTransaction(provider, initializeInstruction, admin);
+    mintTo(
+      provider,
+      admin,
+      poolRewardMint,
+      pool.rewardVault,
+      admin,
+      toToken(5000)
+    );
+  }
+
   function setNextBlockTimestamp(timestamp: number): void {
     const clock = provider.client.getClock();
     provider.client.setClock(
@@ -208,6 +459,8 @@ describe("solana-staking", () => {
           rewardVault: address(rewardVaultPda.toBase58()),
           tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
           rewardRate: 1001, // Invalid: > 1000
+          rewardMode: programClient.RewardMode.FixedRate,
+          rewardPerSecond: 0,
         });
 
         await sendTransaction(provider, initializeInstruction, admin);
@@ -228,6 +481,8 @@ describe("solana-staking", () => {
           rewardVault: address(rewardVaultPda.toBase58()),
           tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
           rewardRate: 0, // Invalid: = 0
+          rewardMode: programClient.RewardMode.FixedRate,
+          rewardPerSecond: 0,
         });
 
         await sendTransaction(provider, initializeInstruction, admin);
@@ -249,6 +504,8 @@ describe("solana-staking", () => {
         rewardVault: address(rewardVaultPda.toBase58()),
         tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
         rewardRate: 500, // 5% daily rate
+        rewardMode: programClient.RewardMode.FixedRate,
+        rewardPerSecond: 0,
       });
 
       // Create and send transaction
@@ -871,11 +1128,14 @@ describe("solana-staking", () => {
 
       const addToBlacklistInstruction =
         programClient.getAddToBlacklistInstruction({
-          admin: adminSigner,
+          authority: adminSigner,
           systemProgram: address(SystemProgram.programId.toBase58()),
           state: address(statePda.toBase58()),
           blacklistEntry: address(blacklistPda.toBase58()),
           address: address(blacklistedUser.publicKey.toBase58()),
+          reasonCode: 0,
+          reference: null,
+          expiresAt: null,
         });
 
       await sendTransaction(provider, addToBlacklistInstruction, admin);
@@ -899,6 +1159,13 @@ describe("solana-staking", () => {
   });
 
   describe("Blacklist", () => {
+    // Closed entries may linger without data, so check the data
+    function isBlacklisted(key: PublicKey) {
+      const blacklistPda = getBlacklistPda(statePda, key);
+      const account = provider.client.getAccount(blacklistPda);
+      return !!account && account.data.length > 0;
+    }
+
     it("should add user to blacklist", async () => {
       const { user: blacklistedUser } = await createTestUser(svm);
 
@@ -924,7 +1191,7 @@ describe("solana-staking", () => {
         expect.fail("Should have thrown an error");
       } catch (error: any) {
         expect(error).to.not.be.null;
-        expect(error.toString()).to.include("already in use");
+        expect(error.toString()).to.include("AddressAlreadyBlacklisted");
       }
     });
 
@@ -985,11 +1252,14 @@ describe("solana-staking", () => {
 
       const addToBlacklistInstruction =
         programClient.getAddToBlacklistInstruction({
-          admin: randomUserSigner,
+          authority: randomUserSigner,
           systemProgram: address(SystemProgram.programId.toBase58()),
           state: address(statePda.toBase58()),
           blacklistEntry: address(blacklistPda.toBase58()),
           address: address(blacklistedUser.publicKey.toBase58()),
+          reasonCode: 0,
+          reference: null,
+          expiresAt: null,
         });
 
       try {
@@ -999,5 +1269,3971 @@ describe("solana-staking", () => {
         expect(error).to.not.be.null;
       }
     });
+
+    it("should add and remove addresses in a batch", async () => {
+      const { user: first } = await createTestUser(svm);
+      const { user: second } = await createTestUser(svm);
+      const { user: alreadyListed } = await createTestUser(svm);
+      await addUserToBlacklist(alreadyListed.publicKey);
+
+      // Duplicates, the zero address and listed addresses are skipped
+      const addresses = [
+        first.publicKey,
+        second.publicKey,
+        first.publicKey,
+        PublicKey.default,
+        alreadyListed.publicKey,
+      ];
+      const addBatchInstruction =
+        programClient.getAddToBlacklistBatchInstruction({
+          authority: adminSigner,
+          state: address(statePda.toBase58()),
+          systemProgram: address(SystemProgram.programId.toBase58()),
+          addresses: addresses.map((key) => address(key.toBase58())),
+          reasonCode: 0,
+          reference: null,
+          expiresAt: null,
+        });
+      await sendTransaction(
+        provider,
+        withRemainingAccounts(
+          addBatchInstruction,
+          addresses.map((key) => getBlacklistPda(statePda, key))
+        ),
+        admin
+      );
+
+      for (const key of [first, second, alreadyListed].map(
+        (user) => user.publicKey
+      )) {
+        const entry = getBlacklistEntry(
+          provider,
+          getBlacklistPda(statePda, key)
+        );
+        expect(entry!.address.toString()).to.equal(key.toBase58());
+      }
+      expect(isBlacklisted(PublicKey.default)).to.be.false;
+
+      // Unlisted addresses are skipped on removal
+      const { user: neverListed } = await createTestUser(svm);
+      const removals = [
+        first.publicKey,
+        neverListed.publicKey,
+        second.publicKey,
+      ];
+      const removeBatchInstruction =
+        programClient.getRemoveFromBlacklistBatchInstruction({
+          authority: adminSigner,
+          state: address(statePda.toBase58()),
+          addresses: removals.map((key) => address(key.toBase58())),
+        });
+      await sendTransaction(
+        provider,
+        withRemainingAccounts(
+          removeBatchInstruction,
+          removals.map((key) => getBlacklistPda(statePda, key))
+        ),
+        admin
+      );
+
+      for (const key of removals) {
+        expect(isBlacklisted(key)).to.be.false;
+      }
+      expect(isBlacklisted(alreadyListed.publicKey)).to.be.true;
+    });
+
+    it("should reject a batch whose accounts do not match", async () => {
+      const { user: first } = await createTestUser(svm);
+      const { user: second } = await createTestUser(svm);
+      const addBatchInstruction =
+        programClient.getAddToBlacklistBatchInstruction({
+          authority: adminSigner,
+          state: address(statePda.toBase58()),
+          systemProgram: address(SystemProgram.programId.toBase58()),
+          addresses: [first, second].map((user) =>
+            address(user.publicKey.toBase58())
+          ),
+          reasonCode: 0,
+          reference: null,
+          expiresAt: null,
+        });
+
+      try {
+        await sendTransaction(
+          provider,
+          withRemainingAccounts(addBatchInstruction, [
+            getBlacklistPda(statePda, second.publicKey),
+            getBlacklistPda(statePda, first.publicKey),
+          ]),
+          admin
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidBlacklistEntry");
+      }
+    });
+
+    it("should record the reason and stop blocking after expiry", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        stakingMint,
+        rewardMint
+      );
+      const blacklistPda = getBlacklistPda(statePda, user.publicKey);
+      const now = Number(provider.client.getClock().unixTimestamp);
+      const reference = new Uint8Array(32).fill(7);
+
+      const addToBlacklistInstruction =
+        programClient.getAddToBlacklistInstruction({
+          authority: adminSigner,
+          systemProgram: address(SystemProgram.programId.toBase58()),
+          state: address(statePda.toBase58()),
+          blacklistEntry: address(blacklistPda.toBase58()),
+          address: address(user.publicKey.toBase58()),
+          reasonCode: 3,
+          reference,
+          expiresAt: now + 3600,
+        });
+      await sendTransaction(provider, addToBlacklistInstruction, admin);
+
+      const entry = getBlacklistEntry(provider, blacklistPda)!;
+      expect(entry.reasonCode).to.equal(3);
+      expect(entry.reference).to.deep.equal({
+        __option: "Some",
+        value: reference,
+      });
+      expect(entry.expiresAt).to.deep.equal({
+        __option: "Some",
+        value: BigInt(now + 3600),
+      });
+
+      try {
+        await stakeTokens(
+          user,
+          userSigner,
+          stakingToken,
+          rewardToken,
+          toToken(10)
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AddressBlacklisted");
+      }
+
+      setNextBlockTimestamp(now + 3600);
+      await stakeTokens(
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(10)
+      );
+
+      const userStakeInfo = getUserStakeInfo(
+        provider,
+        getUserStakePda(statePda, user.publicKey)
+      );
+      expect(userStakeInfo!.amount.toString()).to.equal(toToken(10).toString());
+    });
+
+    it("should re-list addresses whose entries have expired", async () => {
+      const { user: single } = await createTestUser(svm);
+      const { user: batched } = await createTestUser(svm);
+      const now = Number(provider.client.getClock().unixTimestamp);
+
+      const listSingle = (reasonCode: number, expiresAt: number | null) =>
+        sendTransaction(
+          provider,
+          programClient.getAddToBlacklistInstruction({
+            authority: adminSigner,
+            systemProgram: address(SystemProgram.programId.toBase58()),
+            state: address(statePda.toBase58()),
+            blacklistEntry: address(
+              getBlacklistPda(statePda, single.publicKey).toBase58()
+            ),
+            address: address(single.publicKey.toBase58()),
+            reasonCode,
+            reference: null,
+            expiresAt,
+          }),
+          admin
+        );
+      const listBatch = (reasonCode: number, expiresAt: number | null) =>
+        sendTransaction(
+          provider,
+          withRemainingAccounts(
+            programClient.getAddToBlacklistBatchInstruction({
+              authority: adminSigner,
+              state: address(statePda.toBase58()),
+              systemProgram: address(SystemProgram.programId.toBase58()),
+              addresses: [address(batched.publicKey.toBase58())],
+              reasonCode,
+              reference: null,
+              expiresAt,
+            }),
+            [getBlacklistPda(statePda, batched.publicKey)]
+          ),
+          admin
+        );
+
+      await listSingle(1, now + 60);
+      await listBatch(1, now + 60);
+
+      // Still active: the single path rejects, the batch skips
+      try {
+        await listSingle(2, null);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AddressAlreadyBlacklisted");
+      }
+      await listBatch(2, null);
+      const batchedPda = getBlacklistPda(statePda, batched.publicKey);
+      expect(getBlacklistEntry(provider, batchedPda)!.reasonCode).to.equal(1);
+
+      // Once expired, both paths overwrite the entry
+      setNextBlockTimestamp(now + 60);
+      await listSingle(2, null);
+      await listBatch(2, null);
+
+      for (const key of [single.publicKey, batched.publicKey]) {
+        const entry = getBlacklistEntry(
+          provider,
+          getBlacklistPda(statePda, key)
+        )!;
+        expect(entry.reasonCode).to.equal(2);
+        expect(entry.expiresAt).to.deep.equal({ __option: "None" });
+      }
+    });
+
+    it("should reject an expiry in the past", async () => {
+      const { user } = await createTestUser(svm);
+      const now = Number(provider.client.getClock().unixTimestamp);
+
+      const addToBlacklistInstruction =
+        programClient.getAddToBlacklistInstruction({
+          authority: adminSigner,
+          systemProgram: address(SystemProgram.programId.toBase58()),
+          state: address(statePda.toBase58()),
+          blacklistEntry: address(
+            getBlacklistPda(statePda, user.publicKey).toBase58()
+          ),
+          address: address(user.publicKey.toBase58()),
+          reasonCode: 1,
+          reference: null,
+          expiresAt: now,
+        });
+
+      try {
+        await sendTransaction(provider, addToBlacklistInstruction, admin);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidBlacklistExpiry");
+      }
+    });
+
+    it("should ignore lamports sent to an unused blacklist address", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        stakingMint,
+        rewardMint
+      );
+
+      await sendWeb3Transaction(
+        provider,
+        SystemProgram.transfer({
+          fromPubkey: admin.publicKey,
+          toPubkey: getBlacklistPda(statePda, user.publicKey),
+          lamports: 1_000_000,
+        }),
+        [admin]
+      );
+
+      await stakeTokens(
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(10)
+      );
+      const userStakeInfo = getUserStakeInfo(
+        provider,
+        getUserStakePda(statePda, user.publicKey)
+      );
+      expect(userStakeInfo!.amount.toString()).to.equal(toToken(10).toString());
+    });
+  });
+
+  describe("Update Reward Rate", () => {
+    it("should prevent non-admin from updating reward rate", async () => {
+      const { user: randomUser, userSigner: randomUserSigner } =
+        await createTestUser(svm, 5);
+
+      try {
+        await updateRewardRate(randomUser, randomUserSigner, 200);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error).to.not.be.null;
+      }
+    });
+
+    it("should fail with invalid reward rate", async () => {
+      for (const invalidRate of [0, 1001]) {
+        try {
+          await updateRewardRate(admin, adminSigner, invalidRate);
+          expect.fail("Should have thrown an error");
+        } catch (error: any) {
+          expect(error).to.not.be.null;
+          expect(error.toString()).to.include("InvalidRewardRate");
+        }
+      }
+    });
+
+    it("should pay the old rate before the update and the new rate after", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        stakingMint,
+        rewardMint
+      );
+
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeTokens(
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+
+      // One day at the initial 5% rate
+      setNextBlockTimestamp(startTime + SECONDS_IN_A_DAY);
+      await updateRewardRate(admin, adminSigner, 1000);
+
+      const globalState = getGlobalState(provider, statePda);
+      expect(Number(globalState!.rewardRate.toString())).to.equal(1000);
+
+      // One day at the new 10% rate
+      setNextBlockTimestamp(startTime + 2 * SECONDS_IN_A_DAY);
+      const initialBalance = Number(getAccount(provider, rewardToken).amount);
+      await claimUserRewards(user, userSigner, rewardToken);
+
+      // 100 tokens * 5% + 100 tokens * 10% = 15 tokens
+      const rewardsReceived =
+        Number(getAccount(provider, rewardToken).amount) - initialBalance;
+      expect(rewardsReceived).to.equal(Number(toToken(15)));
+
+      // Restore the original rate for any later tests
+      await updateRewardRate(admin, adminSigner, 500);
+    });
+  });
+
+  describe("Withdraw Reward Tokens", () => {
+    it("should allow admin to withdraw reward tokens", async () => {
+      const adminRewardToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        rewardMint,
+        admin.publicKey
+      );
+      const vaultBefore = getAccount(provider, rewardVaultPda).amount;
+      const adminBefore = getAccount(provider, adminRewardToken).amount;
+
+      await withdrawRewardTokens(
+        admin,
+        adminSigner,
+        adminRewardToken,
+        toToken(10)
+      );
+
+      expect(getAccount(provider, rewardVaultPda).amount).to.equal(
+        vaultBefore - toToken(10)
+      );
+      expect(getAccount(provider, adminRewardToken).amount).to.equal(
+        adminBefore + toToken(10)
+      );
+
+      // Return the tokens so later tests keep the same reward budget
+      mintTo(provider, admin, rewardMint, rewardVaultPda, admin, toToken(10));
+    });
+
+    it("should prevent non-admin from withdrawing reward tokens", async () => {
+      const { user: randomUser, userSigner: randomUserSigner } =
+        await createTestUser(svm, 5);
+      const randomUserRewardToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        rewardMint,
+        randomUser.publicKey
+      );
+
+      try {
+        await withdrawRewardTokens(
+          randomUser,
+          randomUserSigner,
+          randomUserRewardToken,
+          toToken(1)
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error).to.not.be.null;
+      }
+    });
+
+    it("should fail when withdrawing zero tokens", async () => {
+      const adminRewardToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        rewardMint,
+        admin.publicKey
+      );
+
+      try {
+        await withdrawRewardTokens(
+          admin,
+          adminSigner,
+          adminRewardToken,
+          BigInt(0)
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error).to.not.be.null;
+        expect(error.toString()).to.include("Cannot withdraw 0 tokens");
+      }
+    });
+  });
+
+  describe("Admin Transfer", () => {
+    it("should transfer admin in two steps", async () => {
+      const { user: newAdmin, userSigner: newAdminSigner } =
+        await createTestUser(svm, 5);
+
+      await proposeAdmin(admin, adminSigner, newAdmin.publicKey);
+
+      let globalState = getGlobalState(provider, statePda);
+      expect(globalState!.admin.toString()).to.equal(
+        admin.publicKey.toBase58()
+      );
+      expect(globalState!.pendingAdmin.toString()).to.equal(
+        newAdmin.publicKey.toBase58()
+      );
+
+      await acceptAdmin(newAdmin, newAdminSigner);
+
+      globalState = getGlobalState(provider, statePda);
+      expect(globalState!.admin.toString()).to.equal(
+        newAdmin.publicKey.toBase58()
+      );
+      expect(globalState!.pendingAdmin.toString()).to.equal(
+        PublicKey.default.toBase58()
+      );
+
+      // Old admin can no longer manage the pool
+      try {
+        await updateRewardRate(admin, adminSigner, 500);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error).to.not.be.null;
+      }
+
+      // Hand the pool back to the original admin for later tests
+      await proposeAdmin(newAdmin, newAdminSigner, admin.publicKey);
+      await acceptAdmin(admin, adminSigner);
+
+      globalState = getGlobalState(provider, statePda);
+      expect(globalState!.admin.toString()).to.equal(
+        admin.publicKey.toBase58()
+      );
+    });
+
+    it("should prevent anyone but the pending admin from accepting", async () => {
+      const { user: proposedAdmin } = await createTestUser(svm, 5);
+      const { user: randomUser, userSigner: randomUserSigner } =
+        await createTestUser(svm, 5);
+
+      await proposeAdmin(admin, adminSigner, proposedAdmin.publicKey);
+
+      try {
+        await acceptAdmin(randomUser, randomUserSigner);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error).to.not.be.null;
+        expect(error.toString()).to.include("NotPendingAdmin");
+      }
+
+      // Cancel the pending proposal
+      await proposeAdmin(admin, adminSigner, PublicKey.default);
+      const globalState = getGlobalState(provider, statePda);
+      expect(globalState!.pendingAdmin.toString()).to.equal(
+        PublicKey.default.toBase58()
+      );
+    });
+
+    it("should prevent non-admin from proposing a new admin", async () => {
+      const { user: randomUser, userSigner: randomUserSigner } =
+        await createTestUser(svm, 5);
+
+      try {
+        await proposeAdmin(randomUser, randomUserSigner, randomUser.publicKey);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error).to.not.be.null;
+      }
+    });
+  });
+
+  describe("Pause", () => {
+    it("should halt stake and claim while leaving unstake open", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        stakingMint,
+        rewardMint
+      );
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeTokens(
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+
+      await pause(PAUSE_STAKE | PAUSE_CLAIM);
+      let globalState = getGlobalState(provider, statePda);
+      expect(globalState!.paused).to.equal(PAUSE_STAKE | PAUSE_CLAIM);
+
+      try {
+        await stakeTokens(
+          user,
+          userSigner,
+          stakingToken,
+          rewardToken,
+          toToken(10)
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("Operation is paused");
+      }
+
+      try {
+        await claimUserRewards(user, userSigner, rewardToken);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("Operation is paused");
+      }
+
+      // Unstake stays open, but its rewards are held back as unpaid
+      setNextBlockTimestamp(startTime + SECONDS_IN_A_DAY);
+      const rewardBefore = getAccount(provider, rewardToken).amount;
+      await unstakeTokens(
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(40)
+      );
+      expect(getAccount(provider, rewardToken).amount).to.equal(rewardBefore);
+      const unpaidRewards = getUserStakeInfo(
+        provider,
+        getUserStakePda(statePda, user.publicKey)
+      )!.unpaidRewards;
+      expect(unpaidRewards > 0n).to.be.true;
+
+      await unpause(PAUSE_STAKE | PAUSE_CLAIM);
+      globalState = getGlobalState(provider, statePda);
+      expect(globalState!.paused).to.equal(0);
+
+      await stakeTokens(
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(10)
+      );
+      const userStakeInfo = getUserStakeInfo(
+        provider,
+        getUserStakePda(statePda, user.publicKey)
+      );
+      expect(userStakeInfo!.amount.toString()).to.equal(
+        toToken(70).toString()
+      );
+
+      // The held-back rewards are paid once claims resume
+      expect(userStakeInfo!.unpaidRewards).to.equal(0n);
+      const rewardAfter = getAccount(provider, rewardToken).amount;
+      expect(rewardAfter >= rewardBefore + unpaidRewards).to.be.true;
+    });
+
+    it("should halt unstake when its flag is set", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        stakingMint,
+        rewardMint
+      );
+      await stakeTokens(
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+
+      await pause(PAUSE_UNSTAKE);
+      try {
+        await unstakeTokens(
+          user,
+          userSigner,
+          stakingToken,
+          rewardToken,
+          toToken(10)
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("Operation is paused");
+      }
+      await unpause(PAUSE_UNSTAKE);
+    });
+
+    it("should reject invalid pause flags", async () => {
+      try {
+        await pause(0);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidPauseFlags");
+      }
+    });
+  });
+
+  describe("Accumulator Reward Mode", () => {
+    let accStakingMint: PublicKey;
+    let accRewardMint: PublicKey;
+    let pool: Pool;
+
+    before(async () => {
+      accStakingMint = createMint(provider, admin, admin.publicKey, null, 9);
+      accRewardMint = createMint(provider, admin, admin.publicKey, null, 9);
+      pool = getPoolPdas(accStakingMint);
+    });
+
+    it("should reject a fixed reward rate in accumulator mode", async () => {
+      try {
+        const initializeInstruction = programClient.getInitializeInstruction({
+          admin: adminSigner,
+          state: address(pool.state.toBase58()),
+          stakingMint: address(accStakingMint.toBase58()),
+          rewardMint: address(accRewardMint.toBase58()),
+          stakingVault: address(pool.stakingVault.toBase58()),
+          rewardVault: address(pool.rewardVault.toBase58()),
+          tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+          rewardRate: 500,
+          rewardMode: programClient.RewardMode.Accumulator,
+          rewardPerSecond: 0,
+        });
+        await sendTransaction(provider, initializeInstruction, admin);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidRewardPerSecond");
+      }
+    });
+
+    it("should split a fixed emission budget across stakers", async () => {
+      const initializeInstruction = programClient.getInitializeInstruction({
+        admin: adminSigner,
+        state: address(pool.state.toBase58()),
+        stakingMint: address(accStakingMint.toBase58()),
+        rewardMint: address(accRewardMint.toBase58()),
+        stakingVault: address(pool.stakingVault.toBase58()),
+        rewardVault: address(pool.rewardVault.toBase58()),
+        tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+        rewardRate: 0,
+        rewardMode: programClient.RewardMode.Accumulator,
+        rewardPerSecond: toToken(1), // 1 token per second for the whole pool
+      });
+      await sendTransaction(provider, initializeInstruction, admin);
+      mintTo(
+        provider,
+        admin,
+        accRewardMint,
+        pool.rewardVault,
+        admin,
+        toToken(5000)
+      );
+
+      const alice = await createTestUser(svm);
+      const bob = await createTestUser(svm);
+      const aliceTokens = await setupUserWithTokens(
+        provider,
+        admin,
+        alice.user,
+        accStakingMint,
+        accRewardMint
+      );
+      const bobTokens = await setupUserWithTokens(
+        provider,
+        admin,
+        bob.user,
+        accStakingMint,
+        accRewardMint
+      );
+
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeInPool(
+        pool,
+        alice.user,
+        alice.userSigner,
+        aliceTokens.stakingToken,
+        aliceTokens.rewardToken,
+        toToken(100)
+      );
+      await stakeInPool(
+        pool,
+        bob.user,
+        bob.userSigner,
+        bobTokens.stakingToken,
+        bobTokens.rewardToken,
+        toToken(300)
+      );
+
+      // 100 seconds of emissions: 100 tokens split 1:3
+      setNextBlockTimestamp(startTime + 100);
+      await claimInPool(
+        pool,
+        alice.user,
+        alice.userSigner,
+        aliceTokens.rewardToken
+      );
+      await claimInPool(pool, bob.user, bob.userSigner, bobTokens.rewardToken);
+
+      expect(getAccount(provider, aliceTokens.rewardToken).amount).to.equal(
+        toToken(25)
+      );
+      expect(getAccount(provider, bobTokens.rewardToken).amount).to.equal(
+        toToken(75)
+      );
+
+      const aliceStakeInfo = getUserStakeInfo(
+        provider,
+        getUserStakePda(pool.state, alice.user.publicKey)
+      );
+      expect(aliceStakeInfo!.totalClaimed.toString()).to.equal(
+        toToken(25).toString()
+      );
+      // reward_debt now offsets everything accrued so far
+      expect(aliceStakeInfo!.rewardDebt.toString()).to.equal(
+        toToken(25).toString()
+      );
+    });
+  });
+
+  describe("Reward Streams", () => {
+    let streamStakingMint: PublicKey;
+    let partnerMint: PublicKey;
+    let pool: Pool;
+    let stream: ReturnType<typeof getRewardStreamPdas>;
+
+    async function claimStreamRewards(
+      user: Keypair,
+      userSigner: KeyPairSigner,
+      partnerToken: PublicKey,
+      owner: PublicKey = user.publicKey,
+      recipient: PublicKey = owner
+    ) {
+      const claimInstruction = programClient.getClaimStreamRewardsInstruction({
+        user: userSigner,
+        state: address(pool.state.toBase58()),
+        rewardMint: address(partnerMint.toBase58()),
+        userStakeInfo: address(getUserStakePda(pool.state, owner).toBase58()),
+        rewardStream: address(stream.rewardStream.toBase58()),
+        streamVault: address(stream.streamVault.toBase58()),
+        userRewardAccount: address(partnerToken.toBase58()),
+        blacklistEntry: address(getBlacklistPda(pool.state, owner).toBase58()),
+        recipientBlacklistEntry: address(
+          getBlacklistPda(pool.state, recipient).toBase58()
+        ),
+        delegateBlacklistEntry: address(
+          getBlacklistPda(pool.state, user.publicKey).toBase58()
+        ),
+        tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+      });
+      return await sendTransaction(provider, claimInstruction, user);
+    }
+
+    before(async () => {
+      streamStakingMint = createMint(provider, admin, admin.publicKey, null, 9);
+      partnerMint = createMint(provider, admin, admin.publicKey, null, 9);
+      pool = getPoolPdas(streamStakingMint);
+      stream = getRewardStreamPdas(pool.state, 0);
+      await initializePool(pool, streamStakingMint, rewardMint);
+    });
+
+    it("should register an extra reward stream", async () => {
+      const addStreamInstruction = programClient.getAddRewardStreamInstruction({
+        admin: adminSigner,
+        state: address(pool.state.toBase58()),
+        rewardStream: address(stream.rewardStream.toBase58()),
+        rewardMint: address(partnerMint.toBase58()),
+        streamVault: address(stream.streamVault.toBase58()),
+        tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+        rewardPerSecond: toToken(1),
+      });
+      await sendTransaction(provider, addStreamInstruction, admin);
+      mintTo(
+        provider,
+        admin,
+        partnerMint,
+        stream.streamVault,
+        admin,
+        toToken(5000)
+      );
+
+      const globalState = getGlobalState(provider, pool.state);
+      expect(globalState!.rewardStreamCount).to.equal(1);
+    });
+
+    it("should require every stream when the stake amount changes", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        streamStakingMint,
+        rewardMint
+      );
+
+      try {
+        await stakeInPool(
+          pool,
+          user,
+          userSigner,
+          stakingToken,
+          rewardToken,
+          toToken(100)
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("MissingRewardStreams");
+      }
+    });
+
+    it("should pay partner rewards pro rata per stream", async () => {
+      const alice = await createTestUser(svm);
+      const bob = await createTestUser(svm);
+      const aliceTokens = await setupUserWithTokens(
+        provider,
+        admin,
+        alice.user,
+        streamStakingMint,
+        rewardMint
+      );
+      const bobTokens = await setupUserWithTokens(
+        provider,
+        admin,
+        bob.user,
+        streamStakingMint,
+        rewardMint
+      );
+      const alicePartnerToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        partnerMint,
+        alice.user.publicKey
+      );
+
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeInPool(
+        pool,
+        alice.user,
+        alice.userSigner,
+        aliceTokens.stakingToken,
+        aliceTokens.rewardToken,
+        toToken(100),
+        [stream.rewardStream]
+      );
+      await stakeInPool(
+        pool,
+        bob.user,
+        bob.userSigner,
+        bobTokens.stakingToken,
+        bobTokens.rewardToken,
+        toToken(300),
+        [stream.rewardStream]
+      );
+
+      // 100 seconds at 1 token/s, alice holds a quarter of the pool
+      setNextBlockTimestamp(startTime + 100);
+      await unstakeInPool(
+        pool,
+        alice.user,
+        alice.userSigner,
+        aliceTokens.stakingToken,
+        aliceTokens.rewardToken,
+        toToken(100),
+        [stream.rewardStream]
+      );
+
+      // Accrued rewards survive a full unstake and stop growing afterwards
+      setNextBlockTimestamp(startTime + 200);
+      await claimStreamRewards(alice.user, alice.userSigner, alicePartnerToken);
+      expect(getAccount(provider, alicePartnerToken).amount).to.equal(
+        toToken(25)
+      );
+    });
+
+    it("should stop stream emissions outside the campaign window", async () => {
+      const carol = await createTestUser(svm);
+      const carolTokens = await setupUserWithTokens(
+        provider,
+        admin,
+        carol.user,
+        streamStakingMint,
+        rewardMint
+      );
+      const carolPartnerToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        partnerMint,
+        carol.user.publicKey
+      );
+
+      // Streams are brought up to date before the window changes
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      const setCampaignInstruction =
+        programClient.getSetRewardCampaignInstruction({
+          admin: adminSigner,
+          state: address(pool.state.toBase58()),
+          rewardsStart: startTime,
+          rewardsEnd: startTime + 100,
+        });
+      await sendTransaction(
+        provider,
+        withRemainingAccounts(setCampaignInstruction, [stream.rewardStream]),
+        admin
+      );
+      await stakeInPool(
+        pool,
+        carol.user,
+        carol.userSigner,
+        carolTokens.stakingToken,
+        carolTokens.rewardToken,
+        toToken(100),
+        [stream.rewardStream]
+      );
+
+      // Only the 100 campaign seconds emit; carol holds a quarter of the pool
+      setNextBlockTimestamp(startTime + 1000);
+      await unstakeInPool(
+        pool,
+        carol.user,
+        carol.userSigner,
+        carolTokens.stakingToken,
+        carolTokens.rewardToken,
+        toToken(100),
+        [stream.rewardStream]
+      );
+      await claimStreamRewards(carol.user, carol.userSigner, carolPartnerToken);
+      expect(getAccount(provider, carolPartnerToken).amount).to.equal(
+        toToken(25)
+      );
+    });
+
+    it("should let the delegate claim stream rewards for the recipient", async () => {
+      const dave = await createTestUser(svm);
+      const daveTokens = await setupUserWithTokens(
+        provider,
+        admin,
+        dave.user,
+        streamStakingMint,
+        rewardMint
+      );
+      const davePartnerToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        partnerMint,
+        dave.user.publicKey
+      );
+      const bot = await createTestUser(svm);
+      const hotWallet = Keypair.generate().publicKey;
+      const hotPartnerToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        partnerMint,
+        hotWallet
+      );
+
+      // Reopen the campaign closed by the previous test
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await sendTransaction(
+        provider,
+        withRemainingAccounts(
+          programClient.getSetRewardCampaignInstruction({
+            admin: adminSigner,
+            state: address(pool.state.toBase58()),
+            rewardsStart: startTime,
+            rewardsEnd: startTime + 365 * SECONDS_IN_A_DAY,
+          }),
+          [stream.rewardStream]
+        ),
+        admin
+      );
+      await stakeInPool(
+        pool,
+        dave.user,
+        dave.userSigner,
+        daveTokens.stakingToken,
+        daveTokens.rewardToken,
+        toToken(100),
+        [stream.rewardStream]
+      );
+      await sendTransaction(
+        provider,
+        programClient.getSetClaimSettingsInstruction({
+          user: dave.userSigner,
+          state: address(pool.state.toBase58()),
+          userStakeInfo: address(
+            getUserStakePda(pool.state, dave.user.publicKey).toBase58()
+          ),
+          rewardRecipient: address(hotWallet.toBase58()),
+          claimDelegate: address(bot.user.publicKey.toBase58()),
+        }),
+        dave.user
+      );
+
+      // The owner's own account is no longer a valid destination
+      setNextBlockTimestamp(startTime + 100);
+      try {
+        await claimStreamRewards(
+          bot.user,
+          bot.userSigner,
+          davePartnerToken,
+          dave.user.publicKey
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidRewardRecipient");
+      }
+
+      // 100 seconds at 1 token/s, dave holds a quarter of the pool
+      await claimStreamRewards(
+        bot.user,
+        bot.userSigner,
+        hotPartnerToken,
+        dave.user.publicKey,
+        hotWallet
+      );
+      expect(getAccount(provider, hotPartnerToken).amount).to.equal(
+        toToken(25)
+      );
+    });
+  });
+
+  describe("Reward Campaigns", () => {
+    let campaignStakingMint: PublicKey;
+    let pool: Pool;
+
+    async function setRewardCampaign(
+      signer: Keypair,
+      signerKeyPair: KeyPairSigner,
+      rewardsStart: number,
+      rewardsEnd: number
+    ) {
+      const setCampaignInstruction =
+        programClient.getSetRewardCampaignInstruction({
+          admin: signerKeyPair,
+          state: address(pool.state.toBase58()),
+          rewardsStart,
+          rewardsEnd,
+        });
+      return await sendTransaction(provider, setCampaignInstruction, signer);
+    }
+
+    before(async () => {
+      campaignStakingMint = createMint(
+        provider,
+        admin,
+        admin.publicKey,
+        null,
+        9
+      );
+      pool = getPoolPdas(campaignStakingMint);
+      await initializePool(pool, campaignStakingMint, rewardMint);
+    });
+
+    it("should only accrue rewards inside the campaign window", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        campaignStakingMint,
+        rewardMint
+      );
+
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+
+      // Campaign opens in one day and runs for one day
+      const campaignStart = startTime + SECONDS_IN_A_DAY;
+      const campaignEnd = campaignStart + SECONDS_IN_A_DAY;
+      await setRewardCampaign(admin, adminSigner, campaignStart, campaignEnd);
+
+      const globalState = getGlobalState(provider, pool.state);
+      expect(globalState!.rewardsStart.toString()).to.equal(
+        campaignStart.toString()
+      );
+      expect(globalState!.rewardsEnd.toString()).to.equal(
+        campaignEnd.toString()
+      );
+
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+
+      // Staking and unstaking still work outside the window
+      setNextBlockTimestamp(startTime + 3 * SECONDS_IN_A_DAY);
+      await unstakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+
+      // Only the single campaign day pays: 100 tokens * 5%
+      expect(getAccount(provider, rewardToken).amount).to.equal(toToken(5));
+    });
+
+    it("should reject an invalid campaign window", async () => {
+      const now = Number(provider.client.getClock().unixTimestamp);
+      try {
+        await setRewardCampaign(admin, adminSigner, now + 100, now + 50);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidRewardWindow");
+      }
+    });
+
+    it("should prevent non-admin from setting a campaign", async () => {
+      const { user: randomUser, userSigner: randomUserSigner } =
+        await createTestUser(svm, 5);
+      const now = Number(provider.client.getClock().unixTimestamp);
+      try {
+        await setRewardCampaign(
+          randomUser,
+          randomUserSigner,
+          now,
+          now + SECONDS_IN_A_DAY
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error).to.not.be.null;
+      }
+    });
+  });
+
+  describe("Lockup Tiers", () => {
+    let lockStakingMint: PublicKey;
+    let pool: Pool;
+
+    before(async () => {
+      lockStakingMint = createMint(provider, admin, admin.publicKey, null, 9);
+      pool = getPoolPdas(lockStakingMint);
+      await initializePool(pool, lockStakingMint, rewardMint);
+    });
+
+    it("should configure lock tiers", async () => {
+      const setLockTiersInstruction = programClient.getSetLockTiersInstruction(
+        {
+          admin: adminSigner,
+          state: address(pool.state.toBase58()),
+          tiers: [
+            { duration: 30 * SECONDS_IN_A_DAY, multiplierBps: 11_000 },
+            { duration: 90 * SECONDS_IN_A_DAY, multiplierBps: 13_000 },
+            { duration: 180 * SECONDS_IN_A_DAY, multiplierBps: 16_000 },
+          ],
+        }
+      );
+      await sendTransaction(provider, setLockTiersInstruction, admin);
+
+      const globalState = getGlobalState(provider, pool.state);
+      expect(globalState!.lockTierCount).to.equal(3);
+      expect(globalState!.lockTiers[2].multiplierBps).to.equal(16_000);
+    });
+
+    it("should reject tiers with a decreasing multiplier", async () => {
+      const setLockTiersInstruction = programClient.getSetLockTiersInstruction(
+        {
+          admin: adminSigner,
+          state: address(pool.state.toBase58()),
+          tiers: [
+            { duration: 30 * SECONDS_IN_A_DAY, multiplierBps: 13_000 },
+            { duration: 90 * SECONDS_IN_A_DAY, multiplierBps: 11_000 },
+          ],
+        }
+      );
+      try {
+        await sendTransaction(provider, setLockTiersInstruction, admin);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidLockTier");
+      }
+    });
+
+    it("should boost rewards and block early withdrawal", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        lockStakingMint,
+        rewardMint
+      );
+
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100),
+        [],
+        0 // 30 days at 1.1x
+      );
+
+      const userStakePda = getUserStakePda(pool.state, user.publicKey);
+      const userStakeInfo = getUserStakeInfo(provider, userStakePda);
+      expect(userStakeInfo!.lockUntil.toString()).to.equal(
+        (startTime + 30 * SECONDS_IN_A_DAY).toString()
+      );
+      expect(userStakeInfo!.multiplierBps).to.equal(11_000);
+
+      setNextBlockTimestamp(startTime + 10 * SECONDS_IN_A_DAY);
+      try {
+        await unstakeInPool(
+          pool,
+          user,
+          userSigner,
+          stakingToken,
+          rewardToken,
+          toToken(100)
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("StakeLocked");
+      }
+
+      // 100 tokens * 5% * 10 days * 1.1
+      await claimInPool(pool, user, userSigner, rewardToken);
+      expect(getAccount(provider, rewardToken).amount).to.equal(toToken(55));
+
+      setNextBlockTimestamp(startTime + 30 * SECONDS_IN_A_DAY);
+      await unstakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+      const finalStakeInfo = getUserStakeInfo(provider, userStakePda);
+      expect(finalStakeInfo!.amount.toString()).to.equal("0");
+      expect(finalStakeInfo!.multiplierBps).to.equal(10_000);
+    });
+
+    it("should stop boosting at lock expiry even without a checkpoint", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        lockStakingMint,
+        rewardMint
+      );
+
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100),
+        [],
+        0 // 30 days at 1.1x
+      );
+
+      // 100 tokens * 5% * (30 days * 1.1 + 60 days)
+      setNextBlockTimestamp(startTime + 90 * SECONDS_IN_A_DAY);
+      await claimInPool(pool, user, userSigner, rewardToken);
+      expect(getAccount(provider, rewardToken).amount).to.equal(toToken(465));
+    });
+
+    it("should boost at the rate in effect across rate changes", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        lockStakingMint,
+        rewardMint
+      );
+      const userStakePda = getUserStakePda(pool.state, user.publicKey);
+
+      const setRate = (newRate: number) =>
+        sendTransaction(
+          provider,
+          programClient.getUpdateRewardRateInstruction({
+            admin: adminSigner,
+            state: address(pool.state.toBase58()),
+            newRate,
+          }),
+          admin
+        );
+      const checkpoint = () =>
+        sendTransaction(
+          provider,
+          withRemainingAccounts(
+            programClient.getCheckpointPositionsInstruction({
+              caller: userSigner,
+              state: address(pool.state.toBase58()),
+            }),
+            [userStakePda]
+          ),
+          user
+        );
+
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100),
+        [],
+        0 // 30 days at 1.1x
+      );
+
+      // Rate doubles while the lock is active
+      setNextBlockTimestamp(startTime + 10 * SECONDS_IN_A_DAY);
+      await setRate(1000);
+
+      // The locked position has not been checkpointed since the change
+      setNextBlockTimestamp(startTime + 20 * SECONDS_IN_A_DAY);
+      try {
+        await setRate(700);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("StaleBoostedPositions");
+      }
+
+      // 100 tokens * 1.1 * (5% * 10 days + 10% * 10 days)
+      await checkpoint();
+      const globalState = getGlobalState(provider, pool.state);
+      expect(globalState!.staleBoostedPositions).to.equal(0);
+      expect(
+        getUserStakeInfo(provider, userStakePda)!.unpaidRewards.toString()
+      ).to.equal(toToken(165).toString());
+
+      // Rate drops back before the lock expires, then doubles after it
+      await setRate(500);
+      setNextBlockTimestamp(startTime + 40 * SECONDS_IN_A_DAY);
+      await checkpoint();
+      await setRate(1000);
+
+      // 165 + 100 tokens * (5% * 10 days * 1.1 + 5% * 10 days + 10% * 20 days)
+      setNextBlockTimestamp(startTime + 60 * SECONDS_IN_A_DAY);
+      await claimInPool(pool, user, userSigner, rewardToken);
+      expect(getAccount(provider, rewardToken).amount).to.equal(toToken(470));
+    });
+  });
+
+  describe("Unbonding Cooldown", () => {
+    const COOLDOWN = 7 * SECONDS_IN_A_DAY;
+    let cooldownStakingMint: PublicKey;
+    let pool: Pool;
+
+    before(async () => {
+      cooldownStakingMint = createMint(
+        provider,
+        admin,
+        admin.publicKey,
+        null,
+        9
+      );
+      pool = getPoolPdas(cooldownStakingMint);
+      await initializePool(pool, cooldownStakingMint, rewardMint);
+
+      const setCooldownInstruction = programClient.getSetCooldownInstruction({
+        admin: adminSigner,
+        state: address(pool.state.toBase58()),
+        cooldownSeconds: COOLDOWN,
+      });
+      await sendTransaction(provider, setCooldownInstruction, admin);
+    });
+
+    function requestUnstakeInstruction(
+      user: Keypair,
+      userSigner: KeyPairSigner,
+      rewardToken: PublicKey,
+      amount: bigint
+    ) {
+      return programClient.getRequestUnstakeInstruction({
+        user: userSigner,
+        state: address(pool.state.toBase58()),
+        rewardMint: poolMints(pool.state).rewardMint,
+        userStakeInfo: address(
+          getUserStakePda(pool.state, user.publicKey).toBase58()
+        ),
+        rewardVault: address(pool.rewardVault.toBase58()),
+        userRewardAccount: address(rewardToken.toBase58()),
+        tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+        blacklistEntry: address(
+          getBlacklistPda(pool.state, user.publicKey).toBase58()
+        ),
+        amount,
+      });
+    }
+
+    function withdrawInstruction(
+      user: Keypair,
+      userSigner: KeyPairSigner,
+      stakingToken: PublicKey
+    ) {
+      return programClient.getWithdrawInstruction({
+        user: userSigner,
+        state: address(pool.state.toBase58()),
+        stakingMint: poolMints(pool.state).stakingMint,
+        userStakeInfo: address(
+          getUserStakePda(pool.state, user.publicKey).toBase58()
+        ),
+        userTokenAccount: address(stakingToken.toBase58()),
+        stakingVault: address(pool.stakingVault.toBase58()),
+        tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+        blacklistEntry: address(
+          getBlacklistPda(pool.state, user.publicKey).toBase58()
+        ),
+      });
+    }
+
+    function cancelUnstakeInstruction(
+      user: Keypair,
+      userSigner: KeyPairSigner,
+      rewardToken: PublicKey
+    ) {
+      return programClient.getCancelUnstakeInstruction({
+        user: userSigner,
+        state: address(pool.state.toBase58()),
+        rewardMint: poolMints(pool.state).rewardMint,
+        userStakeInfo: address(
+          getUserStakePda(pool.state, user.publicKey).toBase58()
+        ),
+        rewardVault: address(pool.rewardVault.toBase58()),
+        userRewardAccount: address(rewardToken.toBase58()),
+        tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+        blacklistEntry: address(
+          getBlacklistPda(pool.state, user.publicKey).toBase58()
+        ),
+      });
+    }
+
+    it("should reject instant unstake while a cooldown is set", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        cooldownStakingMint,
+        rewardMint
+      );
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+
+      try {
+        await unstakeInPool(
+          pool,
+          user,
+          userSigner,
+          stakingToken,
+          rewardToken,
+          toToken(100)
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CooldownRequired");
+      }
+    });
+
+    it("should release tokens only after the cooldown", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        cooldownStakingMint,
+        rewardMint
+      );
+
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+      const balanceAfterStake = getAccount(provider, stakingToken).amount;
+
+      setNextBlockTimestamp(startTime + 10 * SECONDS_IN_A_DAY);
+      await sendTransaction(
+        provider,
+        requestUnstakeInstruction(user, userSigner, rewardToken, toToken(100)),
+        user
+      );
+
+      // 100 tokens * 5% * 10 days, nothing accrues while unbonding
+      expect(getAccount(provider, rewardToken).amount).to.equal(toToken(50));
+      const userStakePda = getUserStakePda(pool.state, user.publicKey);
+      const userStakeInfo = getUserStakeInfo(provider, userStakePda);
+      expect(userStakeInfo!.amount.toString()).to.equal("0");
+      expect(userStakeInfo!.unbondingAmount.toString()).to.equal(
+        toToken(100).toString()
+      );
+
+      setNextBlockTimestamp(startTime + 12 * SECONDS_IN_A_DAY);
+      try {
+        await sendTransaction(
+          provider,
+          withdrawInstruction(user, userSigner, stakingToken),
+          user
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CooldownNotElapsed");
+      }
+
+      setNextBlockTimestamp(startTime + 10 * SECONDS_IN_A_DAY + COOLDOWN);
+      await sendTransaction(
+        provider,
+        withdrawInstruction(user, userSigner, stakingToken),
+        user
+      );
+      expect(getAccount(provider, stakingToken).amount).to.equal(
+        balanceAfterStake + toToken(100)
+      );
+      expect(getAccount(provider, rewardToken).amount).to.equal(toToken(50));
+    });
+
+    it("should return unbonding tokens to the stake on cancel", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        cooldownStakingMint,
+        rewardMint
+      );
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+      await sendTransaction(
+        provider,
+        requestUnstakeInstruction(user, userSigner, rewardToken, toToken(40)),
+        user
+      );
+
+      await sendTransaction(
+        provider,
+        cancelUnstakeInstruction(user, userSigner, rewardToken),
+        user
+      );
+
+      const userStakeInfo = getUserStakeInfo(
+        provider,
+        getUserStakePda(pool.state, user.publicKey)
+      );
+      expect(userStakeInfo!.amount.toString()).to.equal(
+        toToken(100).toString()
+      );
+      expect(userStakeInfo!.unbondingAmount.toString()).to.equal("0");
+    });
+
+    it("should restore the stake timestamp when a full unstake is cancelled", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        cooldownStakingMint,
+        rewardMint
+      );
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+      await sendTransaction(
+        provider,
+        requestUnstakeInstruction(user, userSigner, rewardToken, toToken(100)),
+        user
+      );
+
+      setNextBlockTimestamp(startTime + SECONDS_IN_A_DAY);
+      await sendTransaction(
+        provider,
+        cancelUnstakeInstruction(user, userSigner, rewardToken),
+        user
+      );
+
+      const infoResult = await sendTransaction(
+        provider,
+        programClient.getGetStakeInfoInstruction({
+          state: address(pool.state.toBase58()),
+          userStakeInfo: address(
+            getUserStakePda(pool.state, user.publicKey).toBase58()
+          ),
+          user: address(user.publicKey.toBase58()),
+        }),
+        user
+      );
+      const info = programClient
+        .getStakeInfoViewDecoder()
+        .decode(infoResult.returnData().data());
+      expect(info.amount).to.equal(toToken(100));
+      expect(info.unbondingAmount).to.equal(0n);
+      expect(info.stakeTimestamp).to.equal(
+        BigInt(startTime + SECONDS_IN_A_DAY)
+      );
+    });
+  });
+
+  describe("Receipt Token", () => {
+    let receiptStakingMint: PublicKey;
+    let receiptMint: PublicKey;
+    let extraAccountMetas: PublicKey;
+    let pool: Pool;
+
+    before(async () => {
+      receiptStakingMint = createMint(
+        provider,
+        admin,
+        admin.publicKey,
+        null,
+        9
+      );
+      pool = getPoolPdas(receiptStakingMint);
+      await initializePool(pool, receiptStakingMint, rewardMint);
+      receiptMint = getReceiptMintPda(pool.state);
+      [extraAccountMetas] = PublicKey.findProgramAddressSync(
+        [Buffer.from("extra-account-metas"), receiptMint.toBuffer()],
+        programId
+      );
+
+      const enableReceiptMintInstruction =
+        programClient.getEnableReceiptMintInstruction({
+          admin: adminSigner,
+          state: address(pool.state.toBase58()),
+          stakingMint: address(receiptStakingMint.toBase58()),
+          receiptMint: address(receiptMint.toBase58()),
+          extraAccountMetaList: address(extraAccountMetas.toBase58()),
+          tokenProgram: address(TOKEN_2022_PROGRAM_ID.toBase58()),
+        });
+      await sendTransaction(provider, enableReceiptMintInstruction, admin);
+
+      const setLockTiersInstruction = programClient.getSetLockTiersInstruction(
+        {
+          admin: adminSigner,
+          state: address(pool.state.toBase58()),
+          tiers: [{ duration: 30 * SECONDS_IN_A_DAY, multiplierBps: 11_000 }],
+        }
+      );
+      await sendTransaction(provider, setLockTiersInstruction, admin);
+    });
+
+    async function setupReceiptUser(openStakeAccount = true) {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        receiptStakingMint,
+        rewardMint
+      );
+      const receiptToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        receiptMint,
+        user.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      );
+      if (openStakeAccount) {
+        const openInstruction = programClient.getOpenStakeAccountInstruction({
+          payer: adminSigner,
+          owner: address(user.publicKey.toBase58()),
+          state: address(pool.state.toBase58()),
+          userStakeInfo: address(
+            getUserStakePda(pool.state, user.publicKey).toBase58()
+          ),
+        });
+        await sendTransaction(provider, openInstruction, admin);
+      }
+      return { user, userSigner, stakingToken, rewardToken, receiptToken };
+    }
+
+    type ReceiptUser = Awaited<ReturnType<typeof setupReceiptUser>>;
+
+    async function stakeWithReceipt(
+      holder: ReceiptUser,
+      amount: bigint,
+      lockTier: number | null = null
+    ) {
+      const stakeInstruction = programClient.getStakeInstruction({
+        user: holder.userSigner,
+        state: address(pool.state.toBase58()),
+        stakingMint: poolMints(pool.state).stakingMint,
+        rewardMint: poolMints(pool.state).rewardMint,
+        userStakeInfo: address(
+          getUserStakePda(pool.state, holder.user.publicKey).toBase58()
+        ),
+        userTokenAccount: address(holder.stakingToken.toBase58()),
+        stakingVault: address(pool.stakingVault.toBase58()),
+        rewardVault: address(pool.rewardVault.toBase58()),
+        userRewardAccount: address(holder.rewardToken.toBase58()),
+        tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+        blacklistEntry: address(
+          getBlacklistPda(pool.state, holder.user.publicKey).toBase58()
+        ),
+        receiptMint: address(receiptMint.toBase58()),
+        userReceiptAccount: address(holder.receiptToken.toBase58()),
+        receiptTokenProgram: address(TOKEN_2022_PROGRAM_ID.toBase58()),
+        amount,
+        lockTier,
+        allowlistProof: [],
+      });
+      await sendTransaction(provider, stakeInstruction, holder.user);
+    }
+
+    // Receipt transfers carry the accounts the receipt hook resolves
+    function receiptTransfer(
+      sender: ReceiptUser,
+      receiver: PublicKey,
+      receiverToken: PublicKey,
+      amount: bigint
+    ) {
+      const instruction = createTransferCheckedInstruction(
+        sender.receiptToken,
+        receiptMint,
+        receiverToken,
+        sender.user.publicKey,
+        amount,
+        9,
+        [],
+        TOKEN_2022_PROGRAM_ID
+      );
+      const streams = [0, 1, 2, 3].map(
+        (index) => getRewardStreamPdas(pool.state, index).rewardStream
+      );
+      const extraAccounts: [PublicKey, boolean][] = [
+        [extraAccountMetas, false],
+        [pool.state, true],
+        [getUserStakePda(pool.state, sender.user.publicKey), true],
+        [getUserStakePda(pool.state, receiver), true],
+        [getBlacklistPda(pool.state, sender.user.publicKey), false],
+        [getBlacklistPda(pool.state, receiver), false],
+        ...streams.map((stream): [PublicKey, boolean] => [stream, true]),
+        [programId, false],
+      ];
+      for (const [pubkey, isWritable] of extraAccounts) {
+        instruction.keys.push({ pubkey, isSigner: false, isWritable });
+      }
+      return sendWeb3Transaction(provider, instruction, [sender.user]);
+    }
+
+    async function setBlacklisted(target: PublicKey, listed: boolean) {
+      const blacklistEntry = address(
+        getBlacklistPda(pool.state, target).toBase58()
+      );
+      const instruction = listed
+        ? programClient.getAddToBlacklistInstruction({
+            authority: adminSigner,
+            systemProgram: address(SystemProgram.programId.toBase58()),
+            state: address(pool.state.toBase58()),
+            blacklistEntry,
+            address: address(target.toBase58()),
+            reasonCode: 0,
+            reference: null,
+            expiresAt: null,
+          })
+        : programClient.getRemoveFromBlacklistInstruction({
+            authority: adminSigner,
+            state: address(pool.state.toBase58()),
+            blacklistEntry,
+            address: address(target.toBase58()),
+          });
+      await sendTransaction(provider, instruction, admin);
+    }
+
+    it("should require receipt accounts to stake", async () => {
+      const { user, userSigner, stakingToken, rewardToken } =
+        await setupReceiptUser();
+      try {
+        await stakeInPool(
+          pool,
+          user,
+          userSigner,
+          stakingToken,
+          rewardToken,
+          toToken(100)
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("MissingReceiptAccounts");
+      }
+    });
+
+    it("should move the position with the receipt tokens", async () => {
+      const sender = await setupReceiptUser();
+      const receiver = await setupReceiptUser();
+
+      await stakeWithReceipt(sender, toToken(100));
+      expect(getAccount(provider, sender.receiptToken).amount).to.equal(
+        toToken(100)
+      );
+      const totalStakedBefore = getGlobalState(provider, pool.state)!
+        .totalStaked;
+
+      await receiptTransfer(
+        sender,
+        receiver.user.publicKey,
+        receiver.receiptToken,
+        toToken(40)
+      );
+
+      const senderStakeInfo = getUserStakeInfo(
+        provider,
+        getUserStakePda(pool.state, sender.user.publicKey)
+      );
+      const receiverStakePda = getUserStakePda(
+        pool.state,
+        receiver.user.publicKey
+      );
+      expect(senderStakeInfo!.amount).to.equal(toToken(60));
+      expect(getUserStakeInfo(provider, receiverStakePda)!.amount).to.equal(
+        toToken(40)
+      );
+      expect(getGlobalState(provider, pool.state)!.totalStaked).to.equal(
+        totalStakedBefore
+      );
+
+      const balanceBefore = getAccount(provider, receiver.stakingToken).amount;
+      const unstakeInstruction = programClient.getUnstakeInstruction({
+        user: receiver.userSigner,
+        state: address(pool.state.toBase58()),
+        stakingMint: poolMints(pool.state).stakingMint,
+        rewardMint: poolMints(pool.state).rewardMint,
+        userStakeInfo: address(receiverStakePda.toBase58()),
+        userTokenAccount: address(receiver.stakingToken.toBase58()),
+        stakingVault: address(pool.stakingVault.toBase58()),
+        rewardVault: address(pool.rewardVault.toBase58()),
+        userRewardAccount: address(receiver.rewardToken.toBase58()),
+        tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+        blacklistEntry: address(
+          getBlacklistPda(pool.state, receiver.user.publicKey).toBase58()
+        ),
+        receiptMint: address(receiptMint.toBase58()),
+        userReceiptAccount: address(receiver.receiptToken.toBase58()),
+        receiptTokenProgram: address(TOKEN_2022_PROGRAM_ID.toBase58()),
+        amount: toToken(40),
+      });
+      await sendTransaction(provider, unstakeInstruction, receiver.user);
+
+      expect(getAccount(provider, receiver.stakingToken).amount).to.equal(
+        balanceBefore + toToken(40)
+      );
+      expect(getAccount(provider, receiver.receiptToken).amount).to.equal(
+        BigInt(0)
+      );
+    });
+
+    it("should reject receipts sent to a wallet without a stake account", async () => {
+      const sender = await setupReceiptUser();
+      const receiver = await setupReceiptUser(false);
+      await stakeWithReceipt(sender, toToken(100));
+
+      try {
+        await receiptTransfer(
+          sender,
+          receiver.user.publicKey,
+          receiver.receiptToken,
+          toToken(40)
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AccountNotInitialized");
+      }
+    });
+
+    it("should reject receipt transfers from or to a blacklisted owner", async () => {
+      const sender = await setupReceiptUser();
+      const receiver = await setupReceiptUser();
+      await stakeWithReceipt(sender, toToken(100));
+
+      for (const target of [sender.user.publicKey, receiver.user.publicKey]) {
+        await setBlacklisted(target, true);
+        try {
+          await receiptTransfer(
+            sender,
+            receiver.user.publicKey,
+            receiver.receiptToken,
+            toToken(40)
+          );
+          expect.fail("Should have thrown an error");
+        } catch (error: any) {
+          expect(error.toString()).to.include("AddressBlacklisted");
+        }
+        await setBlacklisted(target, false);
+      }
+
+      expect(
+        getUserStakeInfo(
+          provider,
+          getUserStakePda(pool.state, sender.user.publicKey)
+        )!.amount
+      ).to.equal(toToken(100));
+    });
+
+    it("should keep receipts of a locked position in place", async () => {
+      const sender = await setupReceiptUser();
+      const receiver = await setupReceiptUser();
+      await stakeWithReceipt(sender, toToken(100), 0);
+
+      try {
+        await receiptTransfer(
+          sender,
+          receiver.user.publicKey,
+          receiver.receiptToken,
+          toToken(40)
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("StakeLocked");
+      }
+    });
+
+    it("should reject the receipt hook outside a transfer", async () => {
+      const sender = await setupReceiptUser();
+      const receiver = await setupReceiptUser();
+      await stakeWithReceipt(sender, toToken(100));
+
+      const hookInstruction = programClient.getReceiptTransferHookInstruction({
+        sourceToken: address(sender.receiptToken.toBase58()),
+        receiptMint: address(receiptMint.toBase58()),
+        destinationToken: address(receiver.receiptToken.toBase58()),
+        authority: address(sender.user.publicKey.toBase58()),
+        extraAccountMetaList: address(extraAccountMetas.toBase58()),
+        state: address(pool.state.toBase58()),
+        sourceStakeInfo: address(
+          getUserStakePda(pool.state, sender.user.publicKey).toBase58()
+        ),
+        destinationStakeInfo: address(
+          getUserStakePda(pool.state, receiver.user.publicKey).toBase58()
+        ),
+        sourceBlacklistEntry: address(
+          getBlacklistPda(pool.state, sender.user.publicKey).toBase58()
+        ),
+        destinationBlacklistEntry: address(
+          getBlacklistPda(pool.state, receiver.user.publicKey).toBase58()
+        ),
+        amount: toToken(100),
+      });
+      try {
+        await sendTransaction(provider, hookInstruction, sender.user);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("NotTransferring");
+      }
+    });
+  });
+
+  describe("Compound", () => {
+    let compoundMint: PublicKey;
+    let pool: Pool;
+
+    before(async () => {
+      compoundMint = createMint(provider, admin, admin.publicKey, null, 9);
+      pool = getPoolPdas(compoundMint);
+      await initializePool(pool, compoundMint, compoundMint);
+    });
+
+    function compoundInstruction(
+      pool: Pool,
+      user: Keypair,
+      userSigner: KeyPairSigner
+    ) {
+      return programClient.getCompoundInstruction({
+        user: userSigner,
+        state: address(pool.state.toBase58()),
+        stakingMint: poolMints(pool.state).stakingMint,
+        userStakeInfo: address(
+          getUserStakePda(pool.state, user.publicKey).toBase58()
+        ),
+        stakingVault: address(pool.stakingVault.toBase58()),
+        rewardVault: address(pool.rewardVault.toBase58()),
+        tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+        blacklistEntry: address(
+          getBlacklistPda(pool.state, user.publicKey).toBase58()
+        ),
+      });
+    }
+
+    it("should restake pending rewards without touching the wallet", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        compoundMint,
+        compoundMint
+      );
+
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        stakingToken,
+        toToken(100)
+      );
+      const walletBalance = getAccount(provider, stakingToken).amount;
+
+      // 100 tokens * 5% * 10 days
+      setNextBlockTimestamp(startTime + 10 * SECONDS_IN_A_DAY);
+      await sendTransaction(
+        provider,
+        compoundInstruction(pool, user, userSigner),
+        user
+      );
+
+      const userStakeInfo = getUserStakeInfo(
+        provider,
+        getUserStakePda(pool.state, user.publicKey)
+      );
+      expect(userStakeInfo!.amount.toString()).to.equal(
+        toToken(150).toString()
+      );
+      expect(getGlobalState(provider, pool.state)!.totalStaked).to.equal(
+        toToken(150)
+      );
+      expect(getAccount(provider, pool.stakingVault).amount).to.equal(
+        toToken(150)
+      );
+      expect(getAccount(provider, stakingToken).amount).to.equal(
+        walletBalance
+      );
+    });
+
+    it("should reject compounding when the mints differ", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        stakingMint,
+        rewardMint
+      );
+      await stakeTokens(
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(10)
+      );
+
+      const mainPool = getPoolPdas(stakingMint);
+      try {
+        await sendTransaction(
+          provider,
+          compoundInstruction(mainPool, user, userSigner),
+          user
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CompoundUnavailable");
+      }
+    });
+  });
+
+  describe("Token-2022", () => {
+    let feeMint: PublicKey;
+    let reward2022Mint: PublicKey;
+    let pool: Pool;
+
+    function initialize2022Instruction(
+      pool: Pool,
+      poolStakingMint: PublicKey,
+      poolRewardMint: PublicKey
+    ) {
+      return programClient.getInitializeInstruction({
+        admin: adminSigner,
+        state: address(pool.state.toBase58()),
+        stakingMint: address(poolStakingMint.toBase58()),
+        rewardMint: address(poolRewardMint.toBase58()),
+        stakingVault: address(pool.stakingVault.toBase58()),
+        rewardVault: address(pool.rewardVault.toBase58()),
+        tokenProgram: address(TOKEN_2022_PROGRAM_ID.toBase58()),
+        rewardRate: 500,
+        rewardMode: programClient.RewardMode.FixedRate,
+        rewardPerSecond: 0,
+      });
+    }
+
+    before(async () => {
+      // 1% transfer fee
+      feeMint = createToken2022Mint(
+        provider,
+        admin,
+        admin.publicKey,
+        9,
+        [ExtensionType.TransferFeeConfig],
+        (mint) => [
+          createInitializeTransferFeeConfigInstruction(
+            mint,
+            admin.publicKey,
+            admin.publicKey,
+            100,
+            toToken(1_000_000),
+            TOKEN_2022_PROGRAM_ID
+          ),
+        ]
+      );
+      reward2022Mint = createToken2022Mint(provider, admin, admin.publicKey, 9);
+      pool = getPoolPdas(feeMint);
+      await sendTransaction(
+        provider,
+        initialize2022Instruction(pool, feeMint, reward2022Mint),
+        admin
+      );
+      mintTo(
+        provider,
+        admin,
+        reward2022Mint,
+        pool.rewardVault,
+        admin,
+        toToken(5000),
+        TOKEN_2022_PROGRAM_ID
+      );
+    });
+
+    it("should credit the amount received after transfer fees", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const stakingToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        feeMint,
+        user.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      );
+      const rewardToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        reward2022Mint,
+        user.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      );
+      mintTo(
+        provider,
+        admin,
+        feeMint,
+        stakingToken,
+        admin,
+        toToken(1000),
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      const stakeInstruction = programClient.getStakeInstruction({
+        user: userSigner,
+        state: address(pool.state.toBase58()),
+        stakingMint: address(feeMint.toBase58()),
+        rewardMint: address(reward2022Mint.toBase58()),
+        userStakeInfo: address(
+          getUserStakePda(pool.state, user.publicKey).toBase58()
+        ),
+        userTokenAccount: address(stakingToken.toBase58()),
+        stakingVault: address(pool.stakingVault.toBase58()),
+        rewardVault: address(pool.rewardVault.toBase58()),
+        userRewardAccount: address(rewardToken.toBase58()),
+        tokenProgram: address(TOKEN_2022_PROGRAM_ID.toBase58()),
+        blacklistEntry: address(
+          getBlacklistPda(pool.state, user.publicKey).toBase58()
+        ),
+        amount: toToken(100),
+        lockTier: null,
+        allowlistProof: [],
+      });
+      await sendTransaction(provider, stakeInstruction, user);
+
+      const userStakeInfo = getUserStakeInfo(
+        provider,
+        getUserStakePda(pool.state, user.publicKey)
+      );
+      expect(userStakeInfo!.amount.toString()).to.equal(
+        toToken(99).toString()
+      );
+      expect(getGlobalState(provider, pool.state)!.totalStaked).to.equal(
+        toToken(99)
+      );
+    });
+
+    it("should reject non-transferable mints", async () => {
+      const nonTransferableMint = createToken2022Mint(
+        provider,
+        admin,
+        admin.publicKey,
+        9,
+        [ExtensionType.NonTransferable],
+        (mint) => [
+          createInitializeNonTransferableMintInstruction(
+            mint,
+            TOKEN_2022_PROGRAM_ID
+          ),
+        ]
+      );
+      const badPool = getPoolPdas(nonTransferableMint);
+      try {
+        await sendTransaction(
+          provider,
+          initialize2022Instruction(
+            badPool,
+            nonTransferableMint,
+            reward2022Mint
+          ),
+          admin
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("UnsupportedMintExtension");
+      }
+    });
+  });
+
+  describe("Blacklist Transfer Hook", () => {
+    const hookProgramId = new PublicKey(
+      "Eg5SSDFnSRhxZSXaz1JGTrgesWH66v8SkxhbrumkK499"
+    );
+    let hookedMint: PublicKey;
+    let hookRewardMint: PublicKey;
+    let extraAccountMetas: PublicKey;
+    let pool: Pool;
+
+    // Accounts Token-2022 resolves for the hook, matched by key
+    function hookAccounts(sourceOwner: PublicKey, destinationOwner: PublicKey) {
+      return [
+        extraAccountMetas,
+        hookProgramId,
+        programId,
+        pool.state,
+        getBlacklistPda(pool.state, sourceOwner),
+        getBlacklistPda(pool.state, destinationOwner),
+      ];
+    }
+
+    function hookedTransfer(
+      owner: Keypair,
+      source: PublicKey,
+      destination: PublicKey,
+      destinationOwner: PublicKey,
+      amount: bigint
+    ) {
+      const instruction = createTransferCheckedInstruction(
+        source,
+        hookedMint,
+        destination,
+        owner.publicKey,
+        amount,
+        9,
+        [],
+        TOKEN_2022_PROGRAM_ID
+      );
+      for (const pubkey of hookAccounts(owner.publicKey, destinationOwner)) {
+        instruction.keys.push({ pubkey, isSigner: false, isWritable: false });
+      }
+      return sendWeb3Transaction(provider, instruction, [owner]);
+    }
+
+    async function setupHookedUser() {
+      const { user, userSigner } = await createTestUser(svm);
+      const token = createAssociatedTokenAccount(
+        provider,
+        admin,
+        hookedMint,
+        user.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      );
+      mintTo(
+        provider,
+        admin,
+        hookedMint,
+        token,
+        admin,
+        toToken(1000),
+        TOKEN_2022_PROGRAM_ID
+      );
+      return { user, userSigner, token };
+    }
+
+    before(async () => {
+      svm.addProgram(
+        hookProgramId,
+        fs.readFileSync("./target/deploy/blacklist_hook.so")
+      );
+
+      hookedMint = createToken2022Mint(
+        provider,
+        admin,
+        admin.publicKey,
+        9,
+        [ExtensionType.TransferHook],
+        (mint) => [
+          createInitializeTransferHookInstruction(
+            mint,
+            admin.publicKey,
+            hookProgramId,
+            TOKEN_2022_PROGRAM_ID
+          ),
+        ]
+      );
+      hookRewardMint = createToken2022Mint(
+        provider,
+        admin,
+        admin.publicKey,
+        9
+      );
+      pool = getPoolPdas(hookedMint);
+      const initializeInstruction = programClient.getInitializeInstruction({
+        admin: adminSigner,
+        state: address(pool.state.toBase58()),
+        stakingMint: address(hookedMint.toBase58()),
+        rewardMint: address(hookRewardMint.toBase58()),
+        stakingVault: address(pool.stakingVault.toBase58()),
+        rewardVault: address(pool.rewardVault.toBase58()),
+        tokenProgram: address(TOKEN_2022_PROGRAM_ID.toBase58()),
+        rewardRate: 500,
+        rewardMode: programClient.RewardMode.FixedRate,
+        rewardPerSecond: 0,
+      });
+      await sendTransaction(provider, initializeInstruction, admin);
+
+      [extraAccountMetas] = PublicKey.findProgramAddressSync(
+        [Buffer.from("extra-account-metas"), hookedMint.toBuffer()],
+        hookProgramId
+      );
+      const discriminator = createHash("sha256")
+        .update("global:initialize_extra_account_meta_list")
+        .digest()
+        .subarray(0, 8);
+      await sendWeb3Transaction(
+        provider,
+        new TransactionInstruction({
+          programId: hookProgramId,
+          keys: [
+            { pubkey: admin.publicKey, isSigner: true, isWritable: true },
+            { pubkey: extraAccountMetas, isSigner: false, isWritable: true },
+            { pubkey: hookedMint, isSigner: false, isWritable: false },
+            {
+              pubkey: SystemProgram.programId,
+              isSigner: false,
+              isWritable: false,
+            },
+          ],
+          data: Buffer.from(discriminator),
+        }),
+        [admin]
+      );
+    });
+
+    it("should block transfers to and from blacklisted owners", async () => {
+      const sender = await setupHookedUser();
+      const receiver = await setupHookedUser();
+
+      await hookedTransfer(
+        sender.user,
+        sender.token,
+        receiver.token,
+        receiver.user.publicKey,
+        toToken(10)
+      );
+      expect(getAccount(provider, receiver.token).amount).to.equal(
+        toToken(1010)
+      );
+
+      const addToBlacklistInstruction =
+        programClient.getAddToBlacklistInstruction({
+          authority: adminSigner,
+          systemProgram: address(SystemProgram.programId.toBase58()),
+          state: address(pool.state.toBase58()),
+          blacklistEntry: address(
+            getBlacklistPda(pool.state, receiver.user.publicKey).toBase58()
+          ),
+          address: address(receiver.user.publicKey.toBase58()),
+          reasonCode: 0,
+          reference: null,
+          expiresAt: null,
+        });
+      await sendTransaction(provider, addToBlacklistInstruction, admin);
+
+      try {
+        await hookedTransfer(
+          sender.user,
+          sender.token,
+          receiver.token,
+          receiver.user.publicKey,
+          toToken(10)
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("DestinationBlacklisted");
+      }
+
+      try {
+        await hookedTransfer(
+          receiver.user,
+          receiver.token,
+          sender.token,
+          sender.user.publicKey,
+          toToken(10)
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("SourceBlacklisted");
+      }
+    });
+
+    it("should stake a hooked mint when hook accounts are passed", async () => {
+      const { user, userSigner, token } = await setupHookedUser();
+      const rewardToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        hookRewardMint,
+        user.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      const stakeInstruction = programClient.getStakeInstruction({
+        user: userSigner,
+        state: address(pool.state.toBase58()),
+        stakingMint: address(hookedMint.toBase58()),
+        rewardMint: address(hookRewardMint.toBase58()),
+        userStakeInfo: address(
+          getUserStakePda(pool.state, user.publicKey).toBase58()
+        ),
+        userTokenAccount: address(token.toBase58()),
+        stakingVault: address(pool.stakingVault.toBase58()),
+        rewardVault: address(pool.rewardVault.toBase58()),
+        userRewardAccount: address(rewardToken.toBase58()),
+        tokenProgram: address(TOKEN_2022_PROGRAM_ID.toBase58()),
+        blacklistEntry: address(
+          getBlacklistPda(pool.state, user.publicKey).toBase58()
+        ),
+        amount: toToken(100),
+        lockTier: null,
+        allowlistProof: [],
+      });
+      await sendTransaction(
+        provider,
+        withRemainingAccounts(
+          stakeInstruction,
+          hookAccounts(user.publicKey, pool.state),
+          false
+        ),
+        user
+      );
+
+      expect(getAccount(provider, pool.stakingVault).amount).to.equal(
+        toToken(100)
+      );
+    });
+  });
+
+  describe("Blacklist Manager Role", () => {
+    const role = programClient.Role.BlacklistManager;
+
+    function roleInstruction(grant: boolean, member: PublicKey) {
+      const accounts = {
+        admin: adminSigner,
+        state: address(statePda.toBase58()),
+        roleAssignment: address(
+          getRolePda(statePda, role, member).toBase58()
+        ),
+        role,
+        member: address(member.toBase58()),
+      };
+      return grant
+        ? programClient.getGrantRoleInstruction({
+            ...accounts,
+            systemProgram: address(SystemProgram.programId.toBase58()),
+          })
+        : programClient.getRevokeRoleInstruction(accounts);
+    }
+
+    function managerAddInstruction(
+      manager: Keypair,
+      managerSigner: KeyPairSigner,
+      target: PublicKey
+    ) {
+      return programClient.getAddToBlacklistInstruction({
+        authority: managerSigner,
+        state: address(statePda.toBase58()),
+        blacklistManager: address(
+          getRolePda(statePda, role, manager.publicKey).toBase58()
+        ),
+        blacklistEntry: address(getBlacklistPda(statePda, target).toBase58()),
+        systemProgram: address(SystemProgram.programId.toBase58()),
+        address: address(target.toBase58()),
+        reasonCode: 0,
+        reference: null,
+        expiresAt: null,
+      });
+    }
+
+    it("should let a granted manager add and remove entries", async () => {
+      const { user: manager, userSigner: managerSigner } =
+        await createTestUser(svm);
+      const { user: target } = await createTestUser(svm);
+
+      await sendTransaction(
+        provider,
+        roleInstruction(true, manager.publicKey),
+        admin
+      );
+      await sendTransaction(
+        provider,
+        managerAddInstruction(manager, managerSigner, target.publicKey),
+        manager
+      );
+      expect(
+        getBlacklistEntry(
+          provider,
+          getBlacklistPda(statePda, target.publicKey)
+        )
+      ).to.not.be.null;
+
+      const removeInstruction = programClient.getRemoveFromBlacklistInstruction(
+        {
+          authority: managerSigner,
+          state: address(statePda.toBase58()),
+          blacklistManager: address(
+            getRolePda(statePda, role, manager.publicKey).toBase58()
+          ),
+          blacklistEntry: address(
+            getBlacklistPda(statePda, target.publicKey).toBase58()
+          ),
+          address: address(target.publicKey.toBase58()),
+        }
+      );
+      await sendTransaction(provider, removeInstruction, manager);
+    });
+
+    it("should reject a manager without the role", async () => {
+      const { user: manager, userSigner: managerSigner } =
+        await createTestUser(svm);
+      const { user: target } = await createTestUser(svm);
+
+      // No role account at all
+      const addInstruction = programClient.getAddToBlacklistInstruction({
+        authority: managerSigner,
+        state: address(statePda.toBase58()),
+        blacklistEntry: address(
+          getBlacklistPda(statePda, target.publicKey).toBase58()
+        ),
+        systemProgram: address(SystemProgram.programId.toBase58()),
+        address: address(target.publicKey.toBase58()),
+        reasonCode: 0,
+        reference: null,
+        expiresAt: null,
+      });
+      try {
+        await sendTransaction(provider, addInstruction, manager);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("NotBlacklistManager");
+      }
+
+      // Revoked role
+      await sendTransaction(
+        provider,
+        roleInstruction(true, manager.publicKey),
+        admin
+      );
+      await sendTransaction(
+        provider,
+        roleInstruction(false, manager.publicKey),
+        admin
+      );
+      try {
+        await sendTransaction(
+          provider,
+          managerAddInstruction(manager, managerSigner, target.publicKey),
+          manager
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AccountNotInitialized");
+      }
+    });
+
+    it("should keep managers out of other admin instructions", async () => {
+      const { user: manager, userSigner: managerSigner } =
+        await createTestUser(svm);
+      await sendTransaction(
+        provider,
+        roleInstruction(true, manager.publicKey),
+        admin
+      );
+
+      try {
+        await updateRewardRate(manager, managerSigner, 100);
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ConstraintHasOne");
+      }
+    });
+  });
+
+  describe("Force Unstake", () => {
+    let forceMint: PublicKey;
+    let forceRewardMint: PublicKey;
+    let pool: Pool;
+    const quarantine = Keypair.generate();
+    let quarantineToken: PublicKey;
+    let quarantineRewardToken: PublicKey;
+
+    before(async () => {
+      forceMint = createMint(provider, admin, admin.publicKey, null, 9);
+      forceRewardMint = createMint(provider, admin, admin.publicKey, null, 9);
+      pool = getPoolPdas(forceMint);
+      await initializePool(pool, forceMint, forceRewardMint);
+      quarantineToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        forceMint,
+        quarantine.publicKey
+      );
+      quarantineRewardToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        forceRewardMint,
+        quarantine.publicKey
+      );
+
+      const setQuarantineInstruction =
+        programClient.getSetQuarantineInstruction({
+          admin: adminSigner,
+          state: address(pool.state.toBase58()),
+          quarantine: address(quarantine.publicKey.toBase58()),
+        });
+      await sendTransaction(provider, setQuarantineInstruction, admin);
+    });
+
+    async function stakedUser(amount: bigint) {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        forceMint,
+        forceRewardMint
+      );
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        amount
+      );
+      return user;
+    }
+
+    async function blacklistInPool(target: PublicKey) {
+      const addToBlacklistInstruction =
+        programClient.getAddToBlacklistInstruction({
+          authority: adminSigner,
+          systemProgram: address(SystemProgram.programId.toBase58()),
+          state: address(pool.state.toBase58()),
+          blacklistEntry: address(
+            getBlacklistPda(pool.state, target).toBase58()
+          ),
+          address: address(target.toBase58()),
+          reasonCode: 0,
+          reference: null,
+          expiresAt: null,
+        });
+      await sendTransaction(provider, addToBlacklistInstruction, admin);
+    }
+
+    function forceUnstakeInstruction(
+      target: PublicKey,
+      cancelRewards: boolean
+    ) {
+      return programClient.getForceUnstakeInstruction({
+        admin: adminSigner,
+        state: address(pool.state.toBase58()),
+        stakingMint: address(forceMint.toBase58()),
+        rewardMint: address(forceRewardMint.toBase58()),
+        userStakeInfo: address(
+          getUserStakePda(pool.state, target).toBase58()
+        ),
+        blacklistEntry: address(
+          getBlacklistPda(pool.state, target).toBase58()
+        ),
+        stakingVault: address(pool.stakingVault.toBase58()),
+        rewardVault: address(pool.rewardVault.toBase58()),
+        quarantineTokenAccount: address(quarantineToken.toBase58()),
+        quarantineRewardAccount: cancelRewards
+          ? undefined
+          : address(quarantineRewardToken.toBase58()),
+        tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+        user: address(target.toBase58()),
+        cancelRewards,
+      });
+    }
+
+    it("should move a blacklisted position into quarantine", async () => {
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      const user = await stakedUser(toToken(100));
+      await blacklistInPool(user.publicKey);
+
+      const quarantineBefore = getAccount(provider, quarantineToken).amount;
+      const totalStakedBefore = getGlobalState(provider, pool.state)!
+        .totalStaked;
+
+      // 100 tokens * 5% * 10 days
+      setNextBlockTimestamp(startTime + 10 * SECONDS_IN_A_DAY);
+      await sendTransaction(
+        provider,
+        forceUnstakeInstruction(user.publicKey, false),
+        admin
+      );
+
+      expect(getAccount(provider, quarantineToken).amount).to.equal(
+        quarantineBefore + toToken(100)
+      );
+      expect(getAccount(provider, quarantineRewardToken).amount).to.equal(
+        toToken(50)
+      );
+      expect(getGlobalState(provider, pool.state)!.totalStaked).to.equal(
+        totalStakedBefore - toToken(100)
+      );
+      const userStakeInfo = getUserStakeInfo(
+        provider,
+        getUserStakePda(pool.state, user.publicKey)
+      );
+      expect(userStakeInfo!.amount.toString()).to.equal("0");
+    });
+
+    it("should leave cancelled rewards in the vault", async () => {
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      const user = await stakedUser(toToken(100));
+      await blacklistInPool(user.publicKey);
+
+      const rewardVaultBefore = getAccount(provider, pool.rewardVault).amount;
+      setNextBlockTimestamp(startTime + 10 * SECONDS_IN_A_DAY);
+      await sendTransaction(
+        provider,
+        forceUnstakeInstruction(user.publicKey, true),
+        admin
+      );
+
+      expect(getAccount(provider, pool.rewardVault).amount).to.equal(
+        rewardVaultBefore
+      );
+    });
+
+    it("should refuse positions that are not blacklisted", async () => {
+      const user = await stakedUser(toToken(100));
+
+      try {
+        await sendTransaction(
+          provider,
+          forceUnstakeInstruction(user.publicKey, true),
+          admin
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("AddressNotBlacklisted");
+      }
+    });
+
+    it("should reset the whole position and cancel a reward shortfall", async () => {
+      await sendTransaction(
+        provider,
+        programClient.getSetLockTiersInstruction({
+          admin: adminSigner,
+          state: address(pool.state.toBase58()),
+          tiers: [{ duration: 30 * SECONDS_IN_A_DAY, multiplierBps: 11_000 }],
+        }),
+        admin
+      );
+
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        forceMint,
+        forceRewardMint
+      );
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100),
+        [],
+        0 // 30 days at 1.1x
+      );
+      const userStakePda = getUserStakePda(pool.state, user.publicKey);
+      await sendTransaction(
+        provider,
+        programClient.getSetClaimSettingsInstruction({
+          user: userSigner,
+          state: address(pool.state.toBase58()),
+          userStakeInfo: address(userStakePda.toBase58()),
+          rewardRecipient: address(Keypair.generate().publicKey.toBase58()),
+          claimDelegate: address(Keypair.generate().publicKey.toBase58()),
+        }),
+        user
+      );
+      await blacklistInPool(user.publicKey);
+
+      // Leave 20 tokens in the reward vault
+      const adminRewardToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        forceRewardMint,
+        admin.publicKey
+      );
+      await sendTransaction(
+        provider,
+        programClient.getWithdrawRewardTokensInstruction({
+          admin: adminSigner,
+          state: address(pool.state.toBase58()),
+          rewardMint: address(forceRewardMint.toBase58()),
+          rewardVault: address(pool.rewardVault.toBase58()),
+          destination: address(adminRewardToken.toBase58()),
+          tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+          amount: getAccount(provider, pool.rewardVault).amount - toToken(20),
+        }),
+        admin
+      );
+
+      // 100 tokens * 5% * 10 days * 1.1 owed, 20 paid and 35 cancelled
+      const quarantineRewardsBefore = getAccount(
+        provider,
+        quarantineRewardToken
+      ).amount;
+      setNextBlockTimestamp(startTime + 10 * SECONDS_IN_A_DAY);
+      await sendTransaction(
+        provider,
+        forceUnstakeInstruction(user.publicKey, false),
+        admin
+      );
+      expect(getAccount(provider, quarantineRewardToken).amount).to.equal(
+        quarantineRewardsBefore + toToken(20)
+      );
+
+      const userStakeInfo = getUserStakeInfo(provider, userStakePda)!;
+      expect(userStakeInfo.amount).to.equal(0n);
+      expect(userStakeInfo.unbondingAmount).to.equal(0n);
+      expect(userStakeInfo.unbondingAvailableAt).to.equal(0n);
+      expect(userStakeInfo.lockUntil).to.equal(0n);
+      expect(userStakeInfo.multiplierBps).to.equal(10_000);
+      expect(userStakeInfo.unpaidRewards).to.equal(0n);
+      expect(userStakeInfo.stakeTimestamp).to.equal(0n);
+      expect(userStakeInfo.lastClaimTime).to.equal(0n);
+      expect(userStakeInfo.rewardDebt).to.equal(0n);
+      expect(userStakeInfo.totalClaimed).to.equal(toToken(20));
+      expect(userStakeInfo.rewardRecipient).to.deep.equal({
+        __option: "None",
+      });
+      expect(userStakeInfo.claimDelegate).to.deep.equal({ __option: "None" });
+      expect(userStakeInfo.boostEpoch).to.deep.equal({ __option: "None" });
+      expect(userStakeInfo.allowlistVerified).to.be.false;
+      expect(getGlobalState(provider, pool.state)!.boostedPositions).to.equal(
+        0
+      );
+    });
+  });
+
+  describe("Allowlist", () => {
+    let allowlistMint: PublicKey;
+    let allowlistRewardMint: PublicKey;
+    let pool: Pool;
+
+    // Mirrors verify_allowlist_proof: prefixed sha256 with sorted pairs
+    function sha256(...parts: Uint8Array[]): Uint8Array {
+      const hash = createHash("sha256");
+      parts.forEach((part) => hash.update(part));
+      return new Uint8Array(hash.digest());
+    }
+    const leaf = (wallet: PublicKey) =>
+      sha256(Uint8Array.of(0), wallet.toBytes());
+    const node = (a: Uint8Array, b: Uint8Array) =>
+      Buffer.compare(a, b) <= 0
+        ? sha256(Uint8Array.of(1), a, b)
+        : sha256(Uint8Array.of(1), b, a);
+
+    async function allowlistedUser() {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        allowlistMint,
+        allowlistRewardMint
+      );
+      return { user, userSigner, stakingToken, rewardToken };
+    }
+
+    before(async () => {
+      allowlistMint = createMint(provider, admin, admin.publicKey, null, 9);
+      allowlistRewardMint = createMint(
+        provider,
+        admin,
+        admin.publicKey,
+        null,
+        9
+      );
+      pool = getPoolPdas(allowlistMint);
+      await initializePool(pool, allowlistMint, allowlistRewardMint);
+    });
+
+    it("should admit listed wallets once and reject others", async () => {
+      const listed = await allowlistedUser();
+      const sibling = Keypair.generate().publicKey;
+      const outsider = await allowlistedUser();
+
+      // Two-leaf tree: each leaf's proof is the other leaf
+      const root = node(leaf(listed.user.publicKey), leaf(sibling));
+      const setRootInstruction = programClient.getSetAllowlistRootInstruction({
+        admin: adminSigner,
+        state: address(pool.state.toBase58()),
+        allowlistRoot: root,
+      });
+      await sendTransaction(provider, setRootInstruction, admin);
+
+      await stakeInPool(
+        pool,
+        listed.user,
+        listed.userSigner,
+        listed.stakingToken,
+        listed.rewardToken,
+        toToken(10),
+        [],
+        null,
+        [leaf(sibling)]
+      );
+      const userStakeInfo = getUserStakeInfo(
+        provider,
+        getUserStakePda(pool.state, listed.user.publicKey)
+      );
+      expect(userStakeInfo!.allowlistVerified).to.be.true;
+
+      // Verified wallets skip the proof from then on
+      await stakeInPool(
+        pool,
+        listed.user,
+        listed.userSigner,
+        listed.stakingToken,
+        listed.rewardToken,
+        toToken(10)
+      );
+
+      try {
+        await stakeInPool(
+          pool,
+          outsider.user,
+          outsider.userSigner,
+          outsider.stakingToken,
+          outsider.rewardToken,
+          toToken(10),
+          [],
+          null,
+          [leaf(sibling)]
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("NotAllowlisted");
+      }
+    });
+  });
+
+  describe("Close Stake Account", () => {
+    let closeMint: PublicKey;
+    let closeRewardMint: PublicKey;
+    let pool: Pool;
+
+    before(async () => {
+      closeMint = createMint(provider, admin, admin.publicKey, null, 9);
+      closeRewardMint = createMint(provider, admin, admin.publicKey, null, 9);
+      pool = getPoolPdas(closeMint);
+      await initializePool(pool, closeMint, closeRewardMint);
+    });
+
+    function closeInstruction(user: Keypair, userSigner: KeyPairSigner) {
+      return programClient.getCloseStakeAccountInstruction({
+        user: userSigner,
+        state: address(pool.state.toBase58()),
+        userStakeInfo: address(
+          getUserStakePda(pool.state, user.publicKey).toBase58()
+        ),
+      });
+    }
+
+    it("should reset a fully unstaked position and return its rent", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        closeMint,
+        closeRewardMint
+      );
+      const userStakePda = getUserStakePda(pool.state, user.publicKey);
+
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+
+      try {
+        await sendTransaction(
+          provider,
+          closeInstruction(user, userSigner),
+          user
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("StakeAccountNotEmpty");
+      }
+
+      const now = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(now + SECONDS_IN_A_DAY);
+      await unstakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+
+      const userStakeInfo = getUserStakeInfo(provider, userStakePda)!;
+      expect(userStakeInfo.stakeTimestamp).to.equal(0n);
+      expect(userStakeInfo.lastClaimTime).to.equal(0n);
+      expect(userStakeInfo.rewardDebt).to.equal(0n);
+
+      const balanceBefore = provider.client.getBalance(user.publicKey)!;
+      await sendTransaction(provider, closeInstruction(user, userSigner), user);
+
+      const closedAccount = provider.client.getAccount(userStakePda);
+      expect(!closedAccount || closedAccount.data.length === 0).to.be.true;
+      // The returned rent more than covers the transaction fee
+      expect(provider.client.getBalance(user.publicKey)! > balanceBefore).to.be
+        .true;
+    });
+  });
+
+  describe("Reward Shortfall", () => {
+    let shortMint: PublicKey;
+    let shortRewardMint: PublicKey;
+    let pool: Pool;
+
+    before(async () => {
+      shortMint = createMint(provider, admin, admin.publicKey, null, 9);
+      shortRewardMint = createMint(provider, admin, admin.publicKey, null, 9);
+      pool = getPoolPdas(shortMint);
+      await initializePool(pool, shortMint, shortRewardMint);
+
+      // Leave only 10 tokens in the reward vault
+      const adminRewardToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        shortRewardMint,
+        admin.publicKey
+      );
+      const withdrawInstruction =
+        programClient.getWithdrawRewardTokensInstruction({
+          admin: adminSigner,
+          state: address(pool.state.toBase58()),
+          rewardMint: address(shortRewardMint.toBase58()),
+          rewardVault: address(pool.rewardVault.toBase58()),
+          destination: address(adminRewardToken.toBase58()),
+          tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+          amount: toToken(4990),
+        });
+      await sendTransaction(provider, withdrawInstruction, admin);
+    });
+
+    it("should unstake with a partial payout and settle the rest later", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        shortMint,
+        shortRewardMint
+      );
+      const userStakePda = getUserStakePda(pool.state, user.publicKey);
+      const stakingBefore = getAccount(provider, stakingToken).amount;
+      const rewardBefore = getAccount(provider, rewardToken).amount;
+
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+
+      // 100 tokens * 5% * 10 days = 50 owed, 10 available
+      setNextBlockTimestamp(startTime + 10 * SECONDS_IN_A_DAY);
+      await unstakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+
+      expect(getAccount(provider, stakingToken).amount).to.equal(
+        stakingBefore
+      );
+      expect(getAccount(provider, rewardToken).amount).to.equal(
+        rewardBefore + toToken(10)
+      );
+      expect(getUserStakeInfo(provider, userStakePda)!.unpaidRewards).to.equal(
+        toToken(40)
+      );
+
+      mintTo(
+        provider,
+        admin,
+        shortRewardMint,
+        pool.rewardVault,
+        admin,
+        toToken(100)
+      );
+      await claimInPool(pool, user, userSigner, rewardToken);
+
+      expect(getAccount(provider, rewardToken).amount).to.equal(
+        rewardBefore + toToken(50)
+      );
+      expect(getUserStakeInfo(provider, userStakePda)!.unpaidRewards).to.equal(
+        0n
+      );
+    });
+  });
+
+  describe("Fund Rewards", () => {
+    let fundMint: PublicKey;
+    let fundRewardMint: PublicKey;
+    let pool: Pool;
+
+    before(async () => {
+      fundMint = createMint(provider, admin, admin.publicKey, null, 9);
+      fundRewardMint = createMint(provider, admin, admin.publicKey, null, 9);
+      pool = getPoolPdas(fundMint);
+      await initializePool(pool, fundMint, fundRewardMint);
+    });
+
+    it("should track funding, payouts and admin withdrawals", async () => {
+      const { user: funder, userSigner: funderSigner } =
+        await createTestUser(svm);
+      const funderToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        fundRewardMint,
+        funder.publicKey
+      );
+      mintTo(
+        provider,
+        admin,
+        fundRewardMint,
+        funderToken,
+        admin,
+        toToken(1000)
+      );
+      const vaultBefore = getAccount(provider, pool.rewardVault).amount;
+
+      const fundInstruction = programClient.getFundRewardsInstruction({
+        funder: funderSigner,
+        state: address(pool.state.toBase58()),
+        rewardMint: address(fundRewardMint.toBase58()),
+        funderTokenAccount: address(funderToken.toBase58()),
+        rewardVault: address(pool.rewardVault.toBase58()),
+        tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+        amount: toToken(1000),
+        computeRunway: true,
+      });
+      await sendTransaction(provider, fundInstruction, funder);
+
+      expect(getAccount(provider, pool.rewardVault).amount).to.equal(
+        vaultBefore + toToken(1000)
+      );
+      expect(getGlobalState(provider, pool.state)!.totalFunded).to.equal(
+        toToken(1000)
+      );
+
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        fundMint,
+        fundRewardMint
+      );
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+
+      // 100 tokens * 5% * 2 days
+      setNextBlockTimestamp(startTime + 2 * SECONDS_IN_A_DAY);
+      await claimInPool(pool, user, userSigner, rewardToken);
+
+      expect(getGlobalState(provider, pool.state)!.totalDistributed).to.equal(
+        toToken(10)
+      );
+
+      const withdrawInstruction =
+        programClient.getWithdrawRewardTokensInstruction({
+          admin: adminSigner,
+          state: address(pool.state.toBase58()),
+          rewardMint: address(fundRewardMint.toBase58()),
+          rewardVault: address(pool.rewardVault.toBase58()),
+          destination: address(rewardToken.toBase58()),
+          tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+          amount: toToken(100),
+        });
+      await sendTransaction(provider, withdrawInstruction, admin);
+
+      expect(getGlobalState(provider, pool.state)!.totalWithdrawn).to.equal(
+        toToken(100)
+      );
+    });
+  });
+
+  describe("View Instructions", () => {
+    let viewMint: PublicKey;
+    let viewRewardMint: PublicKey;
+    let pool: Pool;
+
+    before(async () => {
+      viewMint = createMint(provider, admin, admin.publicKey, null, 9);
+      viewRewardMint = createMint(provider, admin, admin.publicKey, null, 9);
+      pool = getPoolPdas(viewMint);
+      await initializePool(pool, viewMint, viewRewardMint);
+    });
+
+    function viewAccounts(wallet: PublicKey) {
+      return {
+        state: address(pool.state.toBase58()),
+        userStakeInfo: address(
+          getUserStakePda(pool.state, wallet).toBase58()
+        ),
+        user: address(wallet.toBase58()),
+      };
+    }
+
+    it("should return stake info and pending rewards as return data", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        viewMint,
+        viewRewardMint
+      );
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+
+      // 100 tokens * 5% * 2 days
+      setNextBlockTimestamp(startTime + 2 * SECONDS_IN_A_DAY);
+      const infoResult = await sendTransaction(
+        provider,
+        programClient.getGetStakeInfoInstruction(viewAccounts(user.publicKey)),
+        user
+      );
+      const info = programClient
+        .getStakeInfoViewDecoder()
+        .decode(infoResult.returnData().data());
+      expect(info.amount).to.equal(toToken(100));
+      expect(info.stakeTimestamp).to.equal(BigInt(startTime));
+      expect(info.pendingRewards).to.equal(toToken(10));
+      expect(info.totalClaimed).to.equal(0n);
+
+      const pendingResult = await sendTransaction(
+        provider,
+        programClient.getPendingRewardsInstruction(
+          viewAccounts(user.publicKey)
+        ),
+        user
+      );
+      expect(
+        Buffer.from(pendingResult.returnData().data()).readBigUInt64LE()
+      ).to.equal(toToken(10));
+
+      // Reading state does not settle anything
+      expect(
+        getUserStakeInfo(provider, getUserStakePda(pool.state, user.publicKey))!
+          .totalClaimed
+      ).to.equal(0n);
+    });
+
+    it("should return zeros for a wallet that never staked", async () => {
+      const { user } = await createTestUser(svm);
+      const stranger = Keypair.generate().publicKey;
+
+      const infoResult = await sendTransaction(
+        provider,
+        programClient.getGetStakeInfoInstruction(viewAccounts(stranger)),
+        user
+      );
+      const info = programClient
+        .getStakeInfoViewDecoder()
+        .decode(infoResult.returnData().data());
+      expect(info.amount).to.equal(0n);
+      expect(info.pendingRewards).to.equal(0n);
+    });
+  });
+
+  describe("Stake For", () => {
+    let forMint: PublicKey;
+    let forRewardMint: PublicKey;
+    let pool: Pool;
+
+    before(async () => {
+      forMint = createMint(provider, admin, admin.publicKey, null, 9);
+      forRewardMint = createMint(provider, admin, admin.publicKey, null, 9);
+      pool = getPoolPdas(forMint);
+      await initializePool(pool, forMint, forRewardMint);
+    });
+
+    async function stakeForInPool(
+      payer: Keypair,
+      payerSigner: KeyPairSigner,
+      payerToken: PublicKey,
+      beneficiary: PublicKey,
+      beneficiaryRewardToken: PublicKey,
+      amount: bigint,
+      recipient: PublicKey = beneficiary
+    ) {
+      const stakeForInstruction = programClient.getStakeForInstruction({
+        payer: payerSigner,
+        state: address(pool.state.toBase58()),
+        stakingMint: address(forMint.toBase58()),
+        rewardMint: address(forRewardMint.toBase58()),
+        userStakeInfo: address(
+          getUserStakePda(pool.state, beneficiary).toBase58()
+        ),
+        payerTokenAccount: address(payerToken.toBase58()),
+        stakingVault: address(pool.stakingVault.toBase58()),
+        rewardVault: address(pool.rewardVault.toBase58()),
+        beneficiaryRewardAccount: address(beneficiaryRewardToken.toBase58()),
+        payerBlacklistEntry: address(
+          getBlacklistPda(pool.state, payer.publicKey).toBase58()
+        ),
+        beneficiaryBlacklistEntry: address(
+          getBlacklistPda(pool.state, beneficiary).toBase58()
+        ),
+        recipientBlacklistEntry: address(
+          getBlacklistPda(pool.state, recipient).toBase58()
+        ),
+        tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+        beneficiary: address(beneficiary.toBase58()),
+        amount,
+        allowlistProof: [],
+      });
+      return await sendTransaction(provider, stakeForInstruction, payer);
+    }
+
+    it("should credit the beneficiary's position with the payer's tokens", async () => {
+      const { user: payer, userSigner: payerSigner } =
+        await createTestUser(svm);
+      const { stakingToken: payerToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        payer,
+        forMint,
+        forRewardMint
+      );
+      const { user: beneficiary, userSigner: beneficiarySigner } =
+        await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        beneficiary,
+        forMint,
+        forRewardMint
+      );
+      const payerBefore = getAccount(provider, payerToken).amount;
+      const stakingBefore = getAccount(provider, stakingToken).amount;
+
+      await stakeForInPool(
+        payer,
+        payerSigner,
+        payerToken,
+        beneficiary.publicKey,
+        rewardToken,
+        toToken(100)
+      );
+
+      expect(getAccount(provider, payerToken).amount).to.equal(
+        payerBefore - toToken(100)
+      );
+      const userStakeInfo = getUserStakeInfo(
+        provider,
+        getUserStakePda(pool.state, beneficiary.publicKey)
+      )!;
+      expect(userStakeInfo.amount).to.equal(toToken(100));
+      expect(userStakeInfo.owner).to.equal(beneficiary.publicKey.toBase58());
+      expect(
+        getUserStakeInfo(
+          provider,
+          getUserStakePda(pool.state, payer.publicKey)
+        )
+      ).to.be.null;
+
+      // Only the beneficiary can take the stake out
+      await unstakeInPool(
+        pool,
+        beneficiary,
+        beneficiarySigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+      expect(getAccount(provider, stakingToken).amount).to.equal(
+        stakingBefore + toToken(100)
+      );
+    });
+
+    it("should pay top-up rewards to the beneficiary's reward recipient", async () => {
+      const { user: payer, userSigner: payerSigner } =
+        await createTestUser(svm);
+      const { stakingToken: payerToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        payer,
+        forMint,
+        forRewardMint
+      );
+      const { user: beneficiary, userSigner: beneficiarySigner } =
+        await createTestUser(svm);
+      const { rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        beneficiary,
+        forMint,
+        forRewardMint
+      );
+      const hotWallet = Keypair.generate().publicKey;
+      const hotRewardToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        forRewardMint,
+        hotWallet
+      );
+
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeForInPool(
+        payer,
+        payerSigner,
+        payerToken,
+        beneficiary.publicKey,
+        rewardToken,
+        toToken(100)
+      );
+      await sendTransaction(
+        provider,
+        programClient.getSetClaimSettingsInstruction({
+          user: beneficiarySigner,
+          state: address(pool.state.toBase58()),
+          userStakeInfo: address(
+            getUserStakePda(pool.state, beneficiary.publicKey).toBase58()
+          ),
+          rewardRecipient: address(hotWallet.toBase58()),
+          claimDelegate: null,
+        }),
+        beneficiary
+      );
+
+      // The beneficiary's own account is no longer a valid destination
+      setNextBlockTimestamp(startTime + 2 * SECONDS_IN_A_DAY);
+      try {
+        await stakeForInPool(
+          payer,
+          payerSigner,
+          payerToken,
+          beneficiary.publicKey,
+          rewardToken,
+          toToken(100)
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidRewardRecipient");
+      }
+
+      // 100 tokens * 5% * 2 days
+      await stakeForInPool(
+        payer,
+        payerSigner,
+        payerToken,
+        beneficiary.publicKey,
+        hotRewardToken,
+        toToken(100),
+        hotWallet
+      );
+      expect(getAccount(provider, hotRewardToken).amount).to.equal(
+        toToken(10)
+      );
+    });
+
+    it("should reject a blacklisted payer or beneficiary", async () => {
+      const { user: payer, userSigner: payerSigner } =
+        await createTestUser(svm);
+      const { stakingToken: payerToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        payer,
+        forMint,
+        forRewardMint
+      );
+      const beneficiary = Keypair.generate().publicKey;
+      const beneficiaryRewardToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        forRewardMint,
+        beneficiary
+      );
+
+      for (const target of [beneficiary, payer.publicKey]) {
+        await sendTransaction(
+          provider,
+          programClient.getAddToBlacklistInstruction({
+            authority: adminSigner,
+            systemProgram: address(SystemProgram.programId.toBase58()),
+            state: address(pool.state.toBase58()),
+            blacklistEntry: address(
+              getBlacklistPda(pool.state, target).toBase58()
+            ),
+            address: address(target.toBase58()),
+            reasonCode: 0,
+            reference: null,
+            expiresAt: null,
+          }),
+          admin
+        );
+
+        try {
+          await stakeForInPool(
+            payer,
+            payerSigner,
+            payerToken,
+            beneficiary,
+            beneficiaryRewardToken,
+            toToken(100)
+          );
+          expect.fail("Should have thrown an error");
+        } catch (error) {
+          expect(error.toString()).to.include("AddressBlacklisted");
+        }
+
+        await sendTransaction(
+          provider,
+          programClient.getRemoveFromBlacklistInstruction({
+            authority: adminSigner,
+            state: address(pool.state.toBase58()),
+            blacklistEntry: address(
+              getBlacklistPda(pool.state, target).toBase58()
+            ),
+            address: address(target.toBase58()),
+          }),
+          admin
+        );
+      }
+    });
+  });
+
+  describe("Delegated Claim", () => {
+    let claimMint: PublicKey;
+    let claimRewardMint: PublicKey;
+    let pool: Pool;
+
+    before(async () => {
+      claimMint = createMint(provider, admin, admin.publicKey, null, 9);
+      claimRewardMint = createMint(provider, admin, admin.publicKey, null, 9);
+      pool = getPoolPdas(claimMint);
+      await initializePool(pool, claimMint, claimRewardMint);
+    });
+
+    async function setClaimSettings(
+      user: Keypair,
+      userSigner: KeyPairSigner,
+      rewardRecipient: PublicKey | null,
+      claimDelegate: PublicKey | null
+    ) {
+      const setClaimSettingsInstruction =
+        programClient.getSetClaimSettingsInstruction({
+          user: userSigner,
+          state: address(pool.state.toBase58()),
+          userStakeInfo: address(
+            getUserStakePda(pool.state, user.publicKey).toBase58()
+          ),
+          rewardRecipient: rewardRecipient
+            ? address(rewardRecipient.toBase58())
+            : null,
+          claimDelegate: claimDelegate
+            ? address(claimDelegate.toBase58())
+            : null,
+        });
+      await sendTransaction(provider, setClaimSettingsInstruction, user);
+    }
+
+    async function claimAs(
+      authority: Keypair,
+      authoritySigner: KeyPairSigner,
+      owner: PublicKey,
+      rewardAccount: PublicKey,
+      recipient: PublicKey
+    ) {
+      const claimInstruction = programClient.getClaimRewardsInstruction({
+        user: authoritySigner,
+        state: address(pool.state.toBase58()),
+        rewardMint: address(claimRewardMint.toBase58()),
+        userStakeInfo: address(getUserStakePda(pool.state, owner).toBase58()),
+        userRewardAccount: address(rewardAccount.toBase58()),
+        rewardVault: address(pool.rewardVault.toBase58()),
+        tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+        blacklistEntry: address(getBlacklistPda(pool.state, owner).toBase58()),
+        recipientBlacklistEntry: address(
+          getBlacklistPda(pool.state, recipient).toBase58()
+        ),
+        delegateBlacklistEntry: address(
+          getBlacklistPda(pool.state, authority.publicKey).toBase58()
+        ),
+      });
+      return await sendTransaction(provider, claimInstruction, authority);
+    }
+
+    it("should let the delegate claim into the registered recipient's account", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        claimMint,
+        claimRewardMint
+      );
+      const { user: bot, userSigner: botSigner } = await createTestUser(svm);
+      const hotWallet = Keypair.generate().publicKey;
+      const hotRewardToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        claimRewardMint,
+        hotWallet
+      );
+
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+      await setClaimSettings(user, userSigner, hotWallet, bot.publicKey);
+
+      // The owner's own account is no longer a valid destination
+      setNextBlockTimestamp(startTime + 2 * SECONDS_IN_A_DAY);
+      try {
+        await claimAs(
+          bot,
+          botSigner,
+          user.publicKey,
+          rewardToken,
+          user.publicKey
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidRewardRecipient");
+      }
+
+      // 100 tokens * 5% * 2 days
+      await claimAs(bot, botSigner, user.publicKey, hotRewardToken, hotWallet);
+      expect(getAccount(provider, hotRewardToken).amount).to.equal(
+        toToken(10)
+      );
+    });
+
+    it("should reject claims from anyone but the owner or delegate", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        claimMint,
+        claimRewardMint
+      );
+      const { user: stranger, userSigner: strangerSigner } =
+        await createTestUser(svm);
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+
+      try {
+        await claimAs(
+          stranger,
+          strangerSigner,
+          user.publicKey,
+          rewardToken,
+          user.publicKey
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("NotClaimAuthority");
+      }
+
+      // Clearing the delegate revokes it
+      await setClaimSettings(user, userSigner, null, stranger.publicKey);
+      await setClaimSettings(user, userSigner, null, null);
+      try {
+        await claimAs(
+          stranger,
+          strangerSigner,
+          user.publicKey,
+          rewardToken,
+          user.publicKey
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("NotClaimAuthority");
+      }
+    });
+
+    it("should reject a blacklisted delegate", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        claimMint,
+        claimRewardMint
+      );
+      const { user: bot, userSigner: botSigner } = await createTestUser(svm);
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+      await setClaimSettings(user, userSigner, null, bot.publicKey);
+      await sendTransaction(
+        provider,
+        programClient.getAddToBlacklistInstruction({
+          authority: adminSigner,
+          systemProgram: address(SystemProgram.programId.toBase58()),
+          state: address(pool.state.toBase58()),
+          blacklistEntry: address(
+            getBlacklistPda(pool.state, bot.publicKey).toBase58()
+          ),
+          address: address(bot.publicKey.toBase58()),
+          reasonCode: 0,
+          reference: null,
+          expiresAt: null,
+        }),
+        admin
+      );
+
+      try {
+        await claimAs(
+          bot,
+          botSigner,
+          user.publicKey,
+          rewardToken,
+          user.publicKey
+        );
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("AddressBlacklisted");
+      }
+    });
+
+    it("should pay unstake rewards to the registered recipient", async () => {
+      const { user, userSigner } = await createTestUser(svm);
+      const { stakingToken, rewardToken } = await setupUserWithTokens(
+        provider,
+        admin,
+        user,
+        claimMint,
+        claimRewardMint
+      );
+      const hotWallet = Keypair.generate().publicKey;
+      const hotRewardToken = createAssociatedTokenAccount(
+        provider,
+        admin,
+        claimRewardMint,
+        hotWallet
+      );
+
+      const startTime = Number(provider.client.getClock().unixTimestamp);
+      setNextBlockTimestamp(startTime);
+      await stakeInPool(
+        pool,
+        user,
+        userSigner,
+        stakingToken,
+        rewardToken,
+        toToken(100)
+      );
+      await setClaimSettings(user, userSigner, hotWallet, null);
+
+      const unstakeTo = (
+        rewardAccount: PublicKey,
+        recipientBlacklistEntry: PublicKey | undefined
+      ) =>
+        sendTransaction(
+          provider,
+          programClient.getUnstakeInstruction({
+            user: userSigner,
+            state: address(pool.state.toBase58()),
+            stakingMint: address(claimMint.toBase58()),
+            rewardMint: address(claimRewardMint.toBase58()),
+            userStakeInfo: address(
+              getUserStakePda(pool.state, user.publicKey).toBase58()
+            ),
+            userTokenAccount: address(stakingToken.toBase58()),
+            stakingVault: address(pool.stakingVault.toBase58()),
+            rewardVault: address(pool.rewardVault.toBase58()),
+            userRewardAccount: address(rewardAccount.toBase58()),
+            tokenProgram: address(TOKEN_PROGRAM_ID.toBase58()),
+            blacklistEntry: address(
+              getBlacklistPda(pool.state, user.publicKey).toBase58()
+            ),
+            recipientBlacklistEntry: recipientBlacklistEntry
+              ? address(recipientBlacklistEntry.toBase58())
+              : undefined,
+            amount: toToken(100),
+          }),
+          user
+        );
+      const recipientEntry = getBlacklistPda(pool.state, hotWallet);
+
+      setNextBlockTimestamp(startTime + 2 * SECONDS_IN_A_DAY);
+      try {
+        await unstakeTo(rewardToken, recipientEntry);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidRewardRecipient");
+      }
+      try {
+        await unstakeTo(hotRewardToken, undefined);
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.toString()).to.include("MissingRecipientBlacklistEntry");
+      }
+
+      // 100 tokens * 5% * 2 days
+      await unstakeTo(hotRewardToken, recipientEntry);
+      expect(getAccount(provider, hotRewardToken).amount).to.equal(
+        toToken(10)
+      );
+    });
   });
 });